@@ -21,6 +21,7 @@ use crate::sprites::SPRITES;
 use std::time::Instant;
 
 use sdl2::event::Event;
+use sdl2::gfx::primitives::DrawRenderer;
 use sdl2::keyboard::Keycode;
 
 use sdl2::pixels;
@@ -35,28 +36,1093 @@ const P2: f32 = PI / 2.0;
 const P3: f32 = 3.0 * PI / 2.0;
 const DR: f32 = 0.0174533;
 const TILE_SIZE: usize = 64;
+const EYE_HEIGHT: f32 = 32.0;
 
+const FACE_NORTH: usize = 0;
+const FACE_EAST: usize = 1;
+const FACE_SOUTH: usize = 2;
+const FACE_WEST: usize = 3;
+
+const DOOR_CLOSED: i32 = 0;
+const DOOR_OPENING: i32 = 1;
+const DOOR_OPEN: i32 = 2;
+const DOOR_CLOSING: i32 = 3;
+const DOOR_SPEED: f32 = 0.001;
+const DOOR_OPEN_TIME: u128 = 3000;
+
+const KEY_RED: i32 = 0;
+const KEY_BLUE: i32 = 1;
+const KEY_YELLOW: i32 = 2;
+const KEY_NAMES: [&str; 3] = ["red", "blue", "yellow"];
+const KEY_COLOURS: [(u8, u8, u8); 3] = [(255, 40, 40), (60, 90, 255), (255, 220, 40)];
+
+const USE_REACH: f32 = 80.0;
+
+const PUSH_READY: i32 = 0;
+const PUSH_MOVING: i32 = 1;
+const PUSH_DONE: i32 = 2;
+const PUSH_SPEED: f32 = 0.05;
+const PUSH_DISTANCE: i32 = 2;
+
+const TRIGGER_NONE: i32 = 0;
+const TRIGGER_EXIT: i32 = 1;
+
+const PORTAL_LIMIT: i32 = 4;
+const MIRROR_LIMIT: i32 = 3;
+
+const DIAGONAL_NONE: i32 = 0;
+const DIAGONAL_NE_SW: i32 = 1;
+const DIAGONAL_NW_SE: i32 = 2;
+
+const FOG_NONE: i32 = 0;
+const FOG_LINEAR: i32 = 1;
+const FOG_EXPONENTIAL: i32 = 2;
+
+const FLASHLIGHT_DRAIN: f32 = 0.001;
+
+/// Properties of a tile id used in `Map::wall_tiles`.
+///
+/// `textures` holds one wall texture per face, indexed by `FACE_NORTH`,
+/// `FACE_EAST`, `FACE_SOUTH` and `FACE_WEST`. A floor or ceiling texture of
+/// -1 falls back to the `floor_tiles` and `ceiling_tiles` layers of the map.
+/// Wall faces bordering this tile are drawn with `jamb_texture`, if set.
+/// Doors with a `lock` other than -1 only open with the matching key.
+/// Switch tiles can be used by the player, see `Switch`, push tiles slide
+/// away when used, see `PushWall`. Thin tiles are drawn as a panel through
+/// the middle of the cell, like doors, and the magenta texels of see-through
+/// tiles let the rays pass. `height` is the wall height in world units, a
+/// full wall is `TILE_SIZE` high. Diagonal tiles hold a wall running corner
+/// to corner through the cell, `DIAGONAL_NE_SW` or `DIAGONAL_NW_SE`; the
+/// north face texture is used on its northern side, the south face on the other.
+/// A `radius` above 0.0 makes the tile a round pillar in the middle of the cell.
+/// Mirror tiles reflect the rays, everything seen in them is multiplied by `tint`.
+struct TileDef {
+    solid: bool,
+    door: bool,
+    lock: i32,
+    switch: bool,
+    push: bool,
+    thin: bool,
+    diagonal: i32,
+    radius: f32,
+    mirror: bool,
+    tint: [f32; 3],
+    see_through: bool,
+    transparent: bool,
+    textures: [i32; 4],
+    height: f32,
+    floor_texture: i32,
+    ceiling_texture: i32,
+    jamb_texture: i32,
+    damage: f32,
+    trigger: i32,
+}
+
+/// A sliding door on a door tile.
+///
+/// `open` goes from 0.0 (closed) to 1.0 (fully open). Horizontal doors run
+/// along the x axis and slide to the west, vertical doors slide to the north.
+/// The `lock` is copied from the door's tile.
+struct Door {
+    x: i32,
+    y: i32,
+    horizontal: bool,
+    lock: i32,
+    open: f32,
+    state: i32,
+    timer: u128,
+}
+
+/// Something the player can use by pressing the use key while facing it.
+trait Interactable {
+    /// Returns false for things the use-ray should pass by.
+    fn can_interact(&self) -> bool;
+
+    /// Reacts to being used, returning a message for the player, if any.
+    fn interact(&mut self, player: &mut Player) -> Option<String>;
+}
+
+impl Interactable for Door {
+    fn can_interact(&self) -> bool {
+        true
+    }
+
+    fn interact(&mut self, player: &mut Player) -> Option<String> {
+        if self.lock >= 0 && !player.keys[self.lock as usize] {
+            return Some(format!(
+                "you need the {} key",
+                KEY_NAMES[self.lock as usize]
+            ));
+        }
+        if self.state == DOOR_CLOSED || self.state == DOOR_CLOSING {
+            self.state = DOOR_OPENING;
+        } else if self.state == DOOR_OPEN {
+            self.state = DOOR_CLOSING;
+        }
+        None
+    }
+}
+
+/// Sets a map cell to one of two tile ids, following the state of a switch.
+struct SwitchLink {
+    x: i32,
+    y: i32,
+    off_tile: i32,
+    on_tile: i32,
+}
+
+/// A wall switch on a switch tile.
+///
+/// Using it flips `on`, swaps its own cell between `off_tile` and `on_tile`
+/// and sets all linked cells to match.
+struct Switch {
+    x: i32,
+    y: i32,
+    on: bool,
+    off_tile: i32,
+    on_tile: i32,
+    links: Vec<SwitchLink>,
+}
+
+impl Interactable for Switch {
+    fn can_interact(&self) -> bool {
+        true
+    }
+
+    fn interact(&mut self, _player: &mut Player) -> Option<String> {
+        self.on = !self.on;
+        None
+    }
+}
+
+/// A secret wall block on a push tile.
+///
+/// Once used it slides `PUSH_DISTANCE` tiles away from the player. While
+/// moving, `x` and `y` is the cell the block is leaving and `offset` how far
+/// it has moved towards the next one.
+struct PushWall {
+    start_x: i32,
+    start_y: i32,
+    x: i32,
+    y: i32,
+    dx: i32,
+    dy: i32,
+    offset: f32,
+    moved: i32,
+    state: i32,
+}
+
+impl PushWall {
+    /// The block moves along the axis the player is facing it from.
+    fn push_direction(&self, player: &Player) -> (i32, i32) {
+        let cx = (self.x * 64 + 32) as f32 - player.x;
+        let cy = (self.y * 64 + 32) as f32 - player.y;
+        if cx.abs() > cy.abs() {
+            return (cx.signum() as i32, 0);
+        }
+        (0, cy.signum() as i32)
+    }
+}
+
+impl Interactable for PushWall {
+    fn can_interact(&self) -> bool {
+        self.state == PUSH_READY
+    }
+
+    fn interact(&mut self, player: &mut Player) -> Option<String> {
+        let (dx, dy) = self.push_direction(player);
+        self.dx = dx;
+        self.dy = dy;
+        self.state = PUSH_MOVING;
+        player.secrets += 1;
+        Some(String::from("you found a secret"))
+    }
+}
+
+/// A wall face that looks out of another face elsewhere in the map.
+///
+/// Rays and the player going into `face` of the wall at (x, y) come out of
+/// `target_face` of the wall at (target_x, target_y), turned to match.
+struct FacePortal {
+    x: i32,
+    y: i32,
+    face: usize,
+    target_x: i32,
+    target_y: i32,
+    target_face: usize,
+}
+
+impl FacePortal {
+    /// How far a direction turns going through the portal, in radians.
+    fn turn(&self) -> f32 {
+        // faces point out of their wall, a ray goes into a face against that.
+        (face_angle(self.target_face) - face_angle(self.face) - PI).rem_euclid(2.0 * PI)
+    }
+
+    /// Moves a point in front of the face to the matching point at the target face.
+    fn carry(&self, x: f32, y: f32) -> (f32, f32) {
+        let (center_x, center_y) = face_center(self.x, self.y, self.face);
+        let (target_center_x, target_center_y) =
+            face_center(self.target_x, self.target_y, self.target_face);
+        let turn = self.turn();
+        let offset_x = x - center_x;
+        let offset_y = y - center_y;
+        (
+            target_center_x + offset_x * turn.cos() - offset_y * turn.sin(),
+            target_center_y + offset_x * turn.sin() + offset_y * turn.cos(),
+        )
+    }
+}
+
+/// Direction a wall face points to, as an angle like `Player::angle`.
+fn face_angle(face: usize) -> f32 {
+    ((face as f32 + 3.0) % 4.0) * P2
+}
+
+/// Middle of a wall face.
+fn face_center(mx: i32, my: i32, face: usize) -> (f32, f32) {
+    let angle = face_angle(face);
+    (
+        (mx * 64) as f32 + 32.0 + angle.cos() * 32.0,
+        (my * 64) as f32 + 32.0 + angle.sin() * 32.0,
+    )
+}
+
+/// Links two wall faces both ways.
+fn face_portals(
+    x: i32,
+    y: i32,
+    face: usize,
+    target_x: i32,
+    target_y: i32,
+    target_face: usize,
+) -> Vec<FacePortal> {
+    vec![
+        FacePortal {
+            x,
+            y,
+            face,
+            target_x,
+            target_y,
+            target_face,
+        },
+        FacePortal {
+            x: target_x,
+            y: target_y,
+            face: target_face,
+            target_x: x,
+            target_y: y,
+            target_face: face,
+        },
+    ]
+}
+
+/// A wall of a sector from (x0, y0) to (x1, y1).
+///
+/// A `portal` other than -1 is the index of the sector on the other side, the
+/// wall is then an opening and only steps in the floor or ceiling are drawn.
+struct SectorWall {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    texture: i32,
+    portal: i32,
+}
+
+/// A convex room of a sector map.
+///
+/// The walls go clockwise around the room as seen on the screen, each one
+/// starting where the one before it ends. The whole room is lit at `light`.
+struct Sector {
+    walls: Vec<SectorWall>,
+    floor_height: f32,
+    ceiling_height: f32,
+    floor_texture: i32,
+    ceiling_texture: i32,
+    light: f32,
+}
+
+/// Depth fog of a map, fading everything into `colour` with distance.
+///
+/// Nothing is fogged closer than `start`. Linear fog is complete at `end`,
+/// exponential fog thickens quickly and is nearly complete at `end`.
+struct Fog {
+    mode: i32,
+    colour: (u8, u8, u8),
+    start: f32,
+    end: f32,
+}
+
+impl Fog {
+    /// How much of the fog colour shows at a distance, from 0.0 to 1.0.
+    fn amount(&self, distance: f32) -> f32 {
+        if self.mode == FOG_NONE {
+            return 0.0;
+        }
+        let t = ((distance - self.start) / (self.end - self.start)).max(0.0);
+        if self.mode == FOG_EXPONENTIAL {
+            1.0 - (-3.0 * t).exp()
+        } else {
+            t.min(1.0)
+        }
+    }
+
+    /// Fogs a colour seen at a distance.
+    fn blend(&self, red: f32, green: f32, blue: f32, distance: f32) -> pixels::Color {
+        let amount = self.amount(distance);
+        let mix = |c: f32, f: u8| (c.min(255.0) * (1.0 - amount) + f as f32 * amount) as u8;
+        pixels::Color::RGB(
+            mix(red, self.colour.0),
+            mix(green, self.colour.1),
+            mix(blue, self.colour.2),
+        )
+    }
+}
+
+/// A grid of tiles with floor and ceiling texture layers.
+///
+/// `floor_heights` and `ceiling_heights` give the height of the floor and the
+/// ceiling of every cell in world units, a plain room spans from 0 to `TILE_SIZE`.
+/// `light_levels` holds the brightness at every corner of the cells, one row more
+/// and one column more than the map, from 0.0 for black to 1.0 for full light.
+/// All of it is scaled by `ambient`, dark maps leave the player to the flashlight.
+/// `lights` are the point lights shining on top of that.
+/// A map with `sectors` is made of those instead of the grid, see `sector_level`.
+/// `fog` is drawn over walls, floors, ceilings and sprites.
 struct Map {
     width: i32,
     height: i32,
     wall_tiles: Vec<i32>,
     floor_tiles: Vec<i32>,
     ceiling_tiles: Vec<i32>,
+    floor_heights: Vec<i32>,
+    ceiling_heights: Vec<i32>,
+    light_levels: Vec<f32>,
+    tiles: Vec<TileDef>,
+    doors: Vec<Door>,
+    switches: Vec<Switch>,
+    push_walls: Vec<PushWall>,
+    portals: Vec<FacePortal>,
+    sectors: Vec<Sector>,
+    fog: Fog,
+    ambient: f32,
+    lights: Vec<Light>,
+}
+
+impl Map {
+    /// Returns the tile id of a cell, 0 outside of the map.
+    fn tile_id(&self, mx: i32, my: i32) -> i32 {
+        if mx < 0 || my < 0 || mx >= self.width || my >= self.height {
+            return 0;
+        }
+        self.wall_tiles[(my * self.width + mx) as usize]
+    }
+
+    fn tile(&self, mx: i32, my: i32) -> &TileDef {
+        &self.tiles[self.tile_id(mx, my) as usize]
+    }
+
+    /// Height of the highest wall top of the map, nothing reaches above it.
+    fn highest_wall(&self) -> f32 {
+        let mut highest: f32 = 0.0;
+        for my in 0..self.height {
+            for mx in 0..self.width {
+                let tile = self.tile(mx, my);
+                if !tile.transparent {
+                    highest = highest.max(self.floor_height(mx, my) + tile.height);
+                }
+            }
+        }
+        highest
+    }
+
+    /// Cells outside of the map are always solid, doors until they are fully open.
+    fn is_solid(&self, mx: i32, my: i32) -> bool {
+        if mx < 0 || my < 0 || mx >= self.width || my >= self.height {
+            return true;
+        }
+        if let Some(door) = self.door(mx, my) {
+            return door.open < 1.0;
+        }
+        if self.moving_block(mx, my).is_some() {
+            return true;
+        }
+        self.tile(mx, my).solid
+    }
+
+    fn push_wall_index(&self, mx: i32, my: i32) -> Option<usize> {
+        if !self.tile(mx, my).push {
+            return None;
+        }
+        self.push_walls
+            .iter()
+            .position(|push_wall| push_wall.x == mx && push_wall.y == my)
+    }
+
+    /// Returns the push wall sliding through a cell, if any.
+    fn moving_block(&self, mx: i32, my: i32) -> Option<&PushWall> {
+        self.push_walls.iter().find(|push_wall| {
+            push_wall.state == PUSH_MOVING
+                && ((push_wall.x == mx && push_wall.y == my)
+                    || (push_wall.x + push_wall.dx == mx && push_wall.y + push_wall.dy == my))
+        })
+    }
+
+    /// Returns the door of a cell, as long as a switch hasn't replaced its tile.
+    fn door(&self, mx: i32, my: i32) -> Option<&Door> {
+        if !self.tile(mx, my).door {
+            return None;
+        }
+        self.doors.iter().find(|door| door.x == mx && door.y == my)
+    }
+
+    fn door_mut(&mut self, mx: i32, my: i32) -> Option<&mut Door> {
+        if !self.tile(mx, my).door {
+            return None;
+        }
+        self.doors
+            .iter_mut()
+            .find(|door| door.x == mx && door.y == my)
+    }
+
+    /// Returns true if a push wall may slide into a cell.
+    ///
+    /// Only plain floor takes a block, never a door (even an open one), a switch
+    /// or a trigger, as the block would overwrite them.
+    fn takes_push_wall(&self, mx: i32, my: i32) -> bool {
+        if mx < 0 || my < 0 || mx >= self.width || my >= self.height {
+            return false;
+        }
+        self.tile_id(mx, my) == 0
+            && self.door(mx, my).is_none()
+            && self.switch_index(mx, my).is_none()
+            && self.tile(mx, my).trigger == TRIGGER_NONE
+            && self.moving_block(mx, my).is_none()
+    }
+
+    fn switch_index(&self, mx: i32, my: i32) -> Option<usize> {
+        if !self.tile(mx, my).switch {
+            return None;
+        }
+        self.switches
+            .iter()
+            .position(|switch| switch.x == mx && switch.y == my)
+    }
+
+    /// Writes the state of a switch into `wall_tiles`.
+    fn apply_switch(&mut self, index: usize) {
+        let switch = &self.switches[index];
+        let mut changes = Vec::new();
+        if switch.on {
+            changes.push((switch.x, switch.y, switch.on_tile));
+        } else {
+            changes.push((switch.x, switch.y, switch.off_tile));
+        }
+        for link in switch.links.iter() {
+            if switch.on {
+                changes.push((link.x, link.y, link.on_tile));
+            } else {
+                changes.push((link.x, link.y, link.off_tile));
+            }
+        }
+        for (mx, my, tile) in changes {
+            self.wall_tiles[(my * self.width + mx) as usize] = tile;
+        }
+    }
+
+    fn floor_texture(&self, mx: i32, my: i32) -> i32 {
+        if mx < 0 || my < 0 || mx >= self.width || my >= self.height {
+            return 0;
+        }
+        let texture = self.tile(mx, my).floor_texture;
+        if texture >= 0 {
+            return texture;
+        }
+        self.floor_tiles[(my * self.width + mx) as usize]
+    }
+
+    fn ceiling_texture(&self, mx: i32, my: i32) -> i32 {
+        if mx < 0 || my < 0 || mx >= self.width || my >= self.height {
+            return 0;
+        }
+        let texture = self.tile(mx, my).ceiling_texture;
+        if texture >= 0 {
+            return texture;
+        }
+        self.ceiling_tiles[(my * self.width + mx) as usize]
+    }
+
+    fn floor_height(&self, mx: i32, my: i32) -> f32 {
+        if mx < 0 || my < 0 || mx >= self.width || my >= self.height {
+            return 0.0;
+        }
+        self.floor_heights[(my * self.width + mx) as usize] as f32
+    }
+
+    fn ceiling_height(&self, mx: i32, my: i32) -> f32 {
+        if mx < 0 || my < 0 || mx >= self.width || my >= self.height {
+            return TILE_SIZE as f32;
+        }
+        self.ceiling_heights[(my * self.width + mx) as usize] as f32
+    }
+
+    /// Returns the index of the portal on a face of a wall, -1 if there is none.
+    fn portal_index(&self, mx: i32, my: i32, face: usize) -> i32 {
+        if let Some(i) = self
+            .portals
+            .iter()
+            .position(|portal| portal.x == mx && portal.y == my && portal.face == face)
+        {
+            return i as i32;
+        }
+        -1
+    }
+
+    /// Returns the sector a point lies in.
+    fn sector_at(&self, x: f32, y: f32) -> Option<usize> {
+        self.sectors.iter().position(|sector| {
+            sector.walls.iter().all(|wall| {
+                (wall.x1 - wall.x0) * (y - wall.y0) - (wall.y1 - wall.y0) * (x - wall.x0) >= 0.0
+            })
+        })
+    }
+
+    /// Returns true if a point is inside the level and not in a wall.
+    fn is_open(&self, x: f32, y: f32) -> bool {
+        if self.sectors.is_empty() {
+            return !self.is_solid(x as i32 >> 6, y as i32 >> 6);
+        }
+        self.sector_at(x, y).is_some()
+    }
+
+    /// Returns true if moving from one point to another crosses a sector wall.
+    fn crosses_wall(&self, from_x: f32, from_y: f32, to_x: f32, to_y: f32) -> bool {
+        for sector in self.sectors.iter() {
+            for wall in sector.walls.iter() {
+                if wall.portal >= 0 {
+                    continue;
+                }
+                let segment = (wall.x0, wall.y0, wall.x1, wall.y1);
+                if let Some((t, _)) =
+                    segment_cross(from_x, from_y, to_x - from_x, to_y - from_y, segment)
+                {
+                    if (0.0..=1.0).contains(&t) {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    /// Floor height at a point, in the grid or the sectors.
+    fn floor_height_at(&self, x: f32, y: f32) -> f32 {
+        if self.sectors.is_empty() {
+            return self.floor_height(x as i32 >> 6, y as i32 >> 6);
+        }
+        if let Some(i) = self.sector_at(x, y) {
+            return self.sectors[i].floor_height;
+        }
+        0.0
+    }
+
+    /// Ceiling height at a point, in the grid or the sectors.
+    fn ceiling_height_at(&self, x: f32, y: f32) -> f32 {
+        if self.sectors.is_empty() {
+            return self.ceiling_height(x as i32 >> 6, y as i32 >> 6);
+        }
+        if let Some(i) = self.sector_at(x, y) {
+            return self.sectors[i].ceiling_height;
+        }
+        TILE_SIZE as f32
+    }
+
+    /// Brightness at a point, blended between the corners of its cell.
+    fn light_at(&self, x: f32, y: f32) -> f32 {
+        if !self.sectors.is_empty() {
+            if let Some(i) = self.sector_at(x, y) {
+                return self.sectors[i].light;
+            }
+            return 1.0;
+        }
+        let corners = self.width + 1;
+        let cx = (x / 64.0).clamp(0.0, self.width as f32 - 0.001);
+        let cy = (y / 64.0).clamp(0.0, self.height as f32 - 0.001);
+        let mx = cx as i32;
+        let my = cy as i32;
+        let fx = cx - mx as f32;
+        let fy = cy - my as f32;
+        let corner = |column: i32, row: i32| self.light_levels[(row * corners + column) as usize];
+        let top = corner(mx, my) * (1.0 - fx) + corner(mx + 1, my) * fx;
+        let bottom = corner(mx, my + 1) * (1.0 - fx) + corner(mx + 1, my + 1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+
+    /// Returns true if no wall stands between a light and a point.
+    ///
+    /// In the grid every cell on the way is checked, stepping from border to
+    /// border like `cast_cells`. Doors don't shadow their own panel and light
+    /// shines through see-through walls. In a sector map only the solid sector
+    /// walls block the light, portals let it pass.
+    fn light_reaches(&self, light_x: f32, light_y: f32, x: f32, y: f32) -> bool {
+        let dx = x - light_x;
+        let dy = y - light_y;
+        if !self.sectors.is_empty() {
+            for sector in self.sectors.iter() {
+                for wall in sector.walls.iter().filter(|wall| wall.portal < 0) {
+                    let segment = (wall.x0, wall.y0, wall.x1, wall.y1);
+                    if let Some((t, _)) = segment_cross(light_x, light_y, dx, dy, segment) {
+                        if t > 0.0 && t < 1.0 {
+                            return false;
+                        }
+                    }
+                }
+            }
+            return true;
+        }
+        let end_x = x as i32 >> 6;
+        let end_y = y as i32 >> 6;
+        let total = (dx * dx + dy * dy).sqrt();
+        if total < 1.0 {
+            return true;
+        }
+        let angle = dy.atan2(dx);
+        // stop just short of the lit point, so walls don't shadow themselves.
+        let length = total - 0.5;
+        let mut mx = (light_x / 64.0).floor() as i32;
+        let mut my = (light_y / 64.0).floor() as i32;
+        let mut enter = 0.0;
+        while enter <= length {
+            let own_door = mx == end_x && my == end_y && self.door(mx, my).is_some();
+            if self.is_solid(mx, my) && !self.tile(mx, my).see_through && !own_door {
+                return false;
+            }
+            let exit = cell_exit(light_x, light_y, angle, mx, my);
+
+            // step just over the border into the next cell.
+            mx = ((light_x + angle.cos() * (exit + 0.01)) / 64.0).floor() as i32;
+            my = ((light_y + angle.sin() * (exit + 0.01)) / 64.0).floor() as i32;
+            enter = exit;
+        }
+        true
+    }
+
+    /// The light falling on a point, from the light layer and the point lights.
+    fn lighting(&self, x: f32, y: f32, z: f32) -> [f32; 3] {
+        let mut light = [self.light_at(x, y) * self.ambient; 3];
+        for point in self.lights.iter() {
+            let dx = x - point.x;
+            let dy = y - point.y;
+            let dz = z - point.z;
+            let distance = (dx * dx + dy * dy + dz * dz).sqrt();
+            if distance >= point.radius || !self.light_reaches(point.x, point.y, x, y) {
+                continue;
+            }
+            let mut falloff = (1.0 - distance / point.radius).powi(2);
+            if point.cone > 0.0 {
+                let off_beam = ((dy.atan2(dx) - point.angle + PI).rem_euclid(2.0 * PI) - PI).abs();
+                if off_beam >= point.cone {
+                    continue;
+                }
+                falloff *= 1.0 - off_beam / point.cone;
+            }
+            for (channel, colour) in light.iter_mut().zip(point.colour) {
+                *channel += colour * falloff;
+            }
+        }
+        light
+    }
+}
+
+/// Builds a sector wall, see `sector`.
+fn sector_wall(x0: f32, y0: f32, x1: f32, y1: f32, texture: i32, portal: i32) -> SectorWall {
+    SectorWall {
+        x0,
+        y0,
+        x1,
+        y1,
+        texture,
+        portal,
+    }
+}
+
+/// Builds a sector from its corners in clockwise order.
+///
+/// `walls` holds the texture and portal of the wall starting at each corner.
+fn sector(
+    corners: &[(f32, f32)],
+    walls: &[(i32, i32)],
+    floor_height: f32,
+    ceiling_height: f32,
+    floor_texture: i32,
+    ceiling_texture: i32,
+) -> Sector {
+    let mut sector_walls = Vec::new();
+    for i in 0..corners.len() {
+        let (x0, y0) = corners[i];
+        let (x1, y1) = corners[(i + 1) % corners.len()];
+        let (texture, portal) = walls[i];
+        sector_walls.push(sector_wall(x0, y0, x1, y1, texture, portal));
+    }
+    Sector {
+        walls: sector_walls,
+        floor_height,
+        ceiling_height,
+        floor_texture,
+        ceiling_texture,
+        light: 1.0,
+    }
+}
+
+/// Checks in debug builds that every portal wall runs back along a wall of the
+/// sector it leads to.
+fn check_portals(sectors: &[Sector]) {
+    for sector in sectors.iter() {
+        for wall in sector.walls.iter().filter(|wall| wall.portal >= 0) {
+            debug_assert!(
+                sectors[wall.portal as usize]
+                    .walls
+                    .iter()
+                    .any(|back| back.x0 == wall.x1
+                        && back.y0 == wall.y1
+                        && back.x1 == wall.x0
+                        && back.y1 == wall.y0),
+                "portal wall from ({}, {}) to ({}, {}) has no wall leading back",
+                wall.x0,
+                wall.y0,
+                wall.x1,
+                wall.y1
+            );
+        }
+    }
+}
+
+/// A test level made of sectors: an octagonal hall, a dark angled corridor and
+/// a raised room open to the sky, all of it dim enough to need the flashlight.
+fn sector_level() -> Map {
+    let map = Map {
+        width: 0,
+        height: 0,
+        wall_tiles: Vec::new(),
+        floor_tiles: Vec::new(),
+        ceiling_tiles: Vec::new(),
+        floor_heights: Vec::new(),
+        ceiling_heights: Vec::new(),
+        light_levels: Vec::new(),
+        tiles: vec![floor_tile()],
+        doors: Vec::new(),
+        switches: Vec::new(),
+        push_walls: Vec::new(),
+        portals: Vec::new(),
+        fog: Fog {
+            mode: FOG_EXPONENTIAL,
+            colour: (60, 70, 90),
+            start: 100.0,
+            end: 900.0,
+        },
+        ambient: 0.4,
+        lights: Vec::new(),
+        sectors: vec![
+            sector(
+                &[
+                    (200.0, 100.0),
+                    (312.0, 100.0),
+                    (400.0, 188.0),
+                    (400.0, 300.0),
+                    (312.0, 388.0),
+                    (200.0, 388.0),
+                    (112.0, 300.0),
+                    (112.0, 188.0),
+                ],
+                &[
+                    (0, -1),
+                    (1, -1),
+                    (0, -1),
+                    (0, 1),
+                    (1, -1),
+                    (0, -1),
+                    (1, -1),
+                    (5, -1),
+                ],
+                0.0,
+                96.0,
+                1,
+                4,
+            ),
+            Sector {
+                light: 0.35,
+                ..sector(
+                    &[
+                        (400.0, 300.0),
+                        (520.0, 420.0),
+                        (432.0, 508.0),
+                        (312.0, 388.0),
+                    ],
+                    &[(4, -1), (0, 2), (4, -1), (0, 0)],
+                    8.0,
+                    72.0,
+                    2,
+                    2,
+                )
+            },
+            sector(
+                &[
+                    (520.0, 420.0),
+                    (640.0, 440.0),
+                    (680.0, 540.0),
+                    (600.0, 620.0),
+                    (480.0, 600.0),
+                    (432.0, 508.0),
+                ],
+                &[(2, -1), (2, -1), (6, -1), (2, -1), (2, -1), (0, 1)],
+                24.0,
+                128.0,
+                0,
+                0,
+            ),
+        ],
+    };
+    check_portals(&map.sectors);
+    map
+}
+
+/// Creates a push wall for every push tile of the map.
+fn find_push_walls(map: &Map) -> Vec<PushWall> {
+    let mut push_walls = Vec::new();
+    for my in 0..map.height {
+        for mx in 0..map.width {
+            if map.tile(mx, my).push {
+                push_walls.push(PushWall {
+                    start_x: mx,
+                    start_y: my,
+                    x: mx,
+                    y: my,
+                    dx: 0,
+                    dy: 0,
+                    offset: 0.0,
+                    moved: 0,
+                    state: PUSH_READY,
+                });
+            }
+        }
+    }
+    push_walls
+}
+
+/// Doors and thin walls between walls to their west and east run along the
+/// x axis, all others along the y axis.
+fn panel_horizontal(map: &Map, mx: i32, my: i32) -> bool {
+    map.tile(mx - 1, my).solid && map.tile(mx + 1, my).solid
+}
+
+/// Creates a closed door for every door tile of the map.
+fn find_doors(map: &Map) -> Vec<Door> {
+    let mut doors = Vec::new();
+    for my in 0..map.height {
+        for mx in 0..map.width {
+            if map.tile(mx, my).door {
+                doors.push(Door {
+                    x: mx,
+                    y: my,
+                    horizontal: panel_horizontal(map, mx, my),
+                    lock: map.tile(mx, my).lock,
+                    open: 0.0,
+                    state: DOOR_CLOSED,
+                    timer: 0,
+                });
+            }
+        }
+    }
+    doors
+}
+
+/// A plain wall using one of the `RGB_TEXTURES` on every face.
+fn wall_tile(texture: i32) -> TileDef {
+    TileDef {
+        solid: true,
+        door: false,
+        lock: -1,
+        switch: false,
+        push: false,
+        thin: false,
+        diagonal: DIAGONAL_NONE,
+        radius: 0.0,
+        mirror: false,
+        tint: [1.0; 3],
+        see_through: false,
+        transparent: false,
+        textures: [texture; 4],
+        height: TILE_SIZE as f32,
+        floor_texture: -1,
+        ceiling_texture: -1,
+        jamb_texture: -1,
+        damage: 0.0,
+        trigger: TRIGGER_NONE,
+    }
+}
+
+/// An open cell the player can walk through.
+fn floor_tile() -> TileDef {
+    TileDef {
+        solid: false,
+        door: false,
+        lock: -1,
+        switch: false,
+        push: false,
+        thin: false,
+        diagonal: DIAGONAL_NONE,
+        radius: 0.0,
+        mirror: false,
+        tint: [1.0; 3],
+        see_through: false,
+        transparent: true,
+        textures: [0; 4],
+        height: 0.0,
+        floor_texture: -1,
+        ceiling_texture: -1,
+        jamb_texture: -1,
+        damage: 0.0,
+        trigger: TRIGGER_NONE,
+    }
 }
 
 struct Player {
     x: f32,
     y: f32,
     angle: f32,
+    velocity_x: f32,
+    velocity_y: f32,
+    turn_speed: f32,
+    pitch: f32,
+    z: f32,
+    velocity_z: f32,
+    eye_height: f32,
+    health: f32,
+    keys: [bool; 3],
+    secrets: i32,
+    bob_phase: f32,
+    bob_amount: f32,
+    flashlight: bool,
+    battery: f32,
+}
+
+/// Options the player can change while playing.
+///
+/// `head_bob_intensity` scales the head bob, J cycles it through 1.0, 0.25 and 0.5.
+/// With `battery_drain` the flashlight uses up its battery, `FLASHLIGHT_DRAIN`
+/// percent per millisecond.
+struct Settings {
+    head_bob: bool,
+    head_bob_intensity: f32,
+    battery_drain: bool,
+}
+
+/// Tuning values for the player's movement.
+///
+/// Speeds are in map units (or degrees) per millisecond, accelerations and
+/// friction in units per millisecond squared. `step_height` is the highest
+/// floor step the player can walk up without jumping.
+struct Movement {
+    acceleration: f32,
+    max_speed: f32,
+    friction: f32,
+    run_multiplier: f32,
+    turn_acceleration: f32,
+    max_turn_speed: f32,
+    turn_friction: f32,
+    look_speed: f32,
+    max_pitch: f32,
+    jump_speed: f32,
+    gravity: f32,
+    crouch_height: f32,
+    crouch_speed: f32,
+    crouch_multiplier: f32,
+    step_height: f32,
+}
+
+/// Level debugging switches, only active while `enabled` is set.
+struct Debug {
+    enabled: bool,
+    noclip: bool,
+    god_mode: bool,
+    free_fly: bool,
+}
+
+impl Debug {
+    /// Walk through walls. The free-fly camera always ignores collision.
+    fn noclip(&self) -> bool {
+        self.enabled && (self.noclip || self.free_fly)
+    }
+
+    /// Enemies can't hurt the player.
+    fn god_mode(&self) -> bool {
+        self.enabled && self.god_mode
+    }
+
+    /// Fly around without gravity and without triggering the exit.
+    fn free_fly(&self) -> bool {
+        self.enabled && self.free_fly
+    }
+}
+
+/// A light carried by a sprite.
+///
+/// It reaches `radius` world units and dims at random by up to `flicker`,
+/// from 0.0 for a steady light to 1.0 for one that goes out now and then.
+struct PointLight {
+    colour: [f32; 3],
+    radius: f32,
+    flicker: f32,
+}
+
+/// A point light shining in the map this frame, see `update_lights`.
+///
+/// A `cone` above 0.0 makes it a spot light shining along `angle`, reaching
+/// `cone` radians to either side and fading towards the edge.
+struct Light {
+    x: f32,
+    y: f32,
+    z: f32,
+    colour: [f32; 3],
+    radius: f32,
+    angle: f32,
+    cone: f32,
 }
 
 struct Sprite {
     npc_type: i32,
     state: i32,
+    key: i32,
     map: i32,
     x: f32,
     y: f32,
     z: f32,
+    light: Option<PointLight>,
+}
+
+impl Interactable for Sprite {
+    fn can_interact(&self) -> bool {
+        self.state == 1 && (self.npc_type == 1 || self.npc_type == 3)
+    }
+
+    fn interact(&mut self, player: &mut Player) -> Option<String> {
+        if self.npc_type == 1 {
+            // pick up the item.
+            self.state = 0;
+            if self.key >= 0 {
+                player.keys[self.key as usize] = true;
+                return Some(format!(
+                    "picked up the {} key",
+                    KEY_NAMES[self.key as usize]
+                ));
+            }
+        }
+        if self.npc_type == 3 {
+            return Some(String::from("it doesn't want to talk"));
+        }
+        None
+    }
 }
 
 struct Keyboard {
@@ -64,6 +1130,11 @@ struct Keyboard {
     down: bool,
     left: bool,
     right: bool,
+    run: bool,
+    look_up: bool,
+    look_down: bool,
+    jump: bool,
+    crouch: bool,
 }
 
 fn dist(ax: f32, ay: f32, bx: f32, by: f32, _ang: f32) -> f32 {
@@ -77,9 +1148,9 @@ fn main() -> Result<(), String> {
         width: 8,
         height: 8,
         wall_tiles: vec![
-            1, 1, 1, 1, 1, 3, 1, 1, 6, 0, 0, 1, 0, 0, 0, 1, 1, 0, 0, 4, 0, 2, 0, 1, 1, 5, 4, 5, 0,
-            0, 0, 1, 2, 0, 0, 0, 0, 0, 0, 1, 2, 0, 0, 0, 0, 1, 0, 1, 2, 0, 0, 0, 0, 0, 0, 1, 1, 1,
-            3, 1, 3, 1, 3, 1,
+            1, 1, 1, 1, 1, 3, 1, 1, 6, 7, 0, 1, 0, 0, 0, 1, 1, 0, 0, 4, 0, 12, 0, 19, 1, 5, 8, 5,
+            0, 0, 13, 19, 2, 0, 0, 0, 18, 0, 0, 10, 2, 0, 0, 0, 14, 1, 0, 1, 2, 17, 0, 0, 0, 0, 16,
+            1, 1, 1, 15, 1, 3, 1, 3, 1,
         ],
         floor_tiles: vec![
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 2, 0, 1, 0, 0, 0, 0, 0, 1,
@@ -87,58 +1158,265 @@ fn main() -> Result<(), String> {
             0, 0, 0, 0, 0, 0,
         ],
         ceiling_tiles: vec![
-            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0,
             0, 0, 0, 0, 4, 2, 4, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0,
             0, 0, 0, 0, 0, 0,
         ],
+        floor_heights: vec![
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, -24, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 32, 0, 0, 0, 0, 0, 0, 16, 16, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ],
+        ceiling_heights: vec![
+            64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64,
+            64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 96, 64, 64, 64, 64, 64, 64, 64, 64, 64,
+            64, 64, 96, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64, 64,
+        ],
+        light_levels: vec![
+            0.3, 0.3, 0.3, 0.4, 0.6, 0.6, 0.6, 0.6, 0.6, 0.3, 0.2, 0.2, 0.3, 0.6, 0.8, 0.8, 0.6,
+            0.6, 0.3, 0.2, 0.2, 0.3, 0.7, 0.9, 0.7, 0.5, 0.5, 0.4, 0.3, 0.3, 0.5, 0.8, 0.9, 0.6,
+            0.4, 0.4, 0.55, 0.55, 0.55, 0.55, 0.55, 0.55, 0.6, 0.4, 0.4, 0.55, 0.55, 0.55, 0.55,
+            0.55, 0.55, 0.5, 0.3, 0.3, 0.55, 0.55, 0.55, 0.55, 0.55, 0.55, 0.4, 0.3, 0.3, 0.6,
+            0.55, 0.55, 0.55, 0.55, 0.6, 0.4, 0.3, 0.3, 0.6, 0.6, 0.8, 0.8, 0.8, 0.6, 0.4, 0.3,
+            0.3,
+        ],
+        tiles: vec![
+            floor_tile(),
+            wall_tile(0),
+            wall_tile(1),
+            wall_tile(2),
+            TileDef {
+                door: true,
+                lock: KEY_RED,
+                jamb_texture: 6,
+                ..wall_tile(3)
+            },
+            wall_tile(4),
+            wall_tile(5),
+            TileDef {
+                trigger: TRIGGER_EXIT,
+                ..floor_tile()
+            },
+            TileDef {
+                door: true,
+                lock: KEY_BLUE,
+                jamb_texture: 6,
+                ..wall_tile(3)
+            },
+            TileDef {
+                door: true,
+                lock: KEY_YELLOW,
+                jamb_texture: 6,
+                ..wall_tile(3)
+            },
+            TileDef {
+                switch: true,
+                ..wall_tile(7)
+            },
+            TileDef {
+                switch: true,
+                ..wall_tile(9)
+            },
+            TileDef {
+                push: true,
+                ..wall_tile(1)
+            },
+            TileDef {
+                thin: true,
+                see_through: true,
+                ..wall_tile(10)
+            },
+            TileDef {
+                height: 32.0,
+                ..wall_tile(5)
+            },
+            TileDef {
+                height: 128.0,
+                ..wall_tile(2)
+            },
+            TileDef {
+                solid: false,
+                diagonal: DIAGONAL_NE_SW,
+                ..wall_tile(0)
+            },
+            TileDef {
+                solid: false,
+                diagonal: DIAGONAL_NW_SE,
+                ..wall_tile(0)
+            },
+            TileDef {
+                solid: false,
+                radius: 16.0,
+                ..wall_tile(2)
+            },
+            TileDef {
+                mirror: true,
+                tint: [0.75, 0.85, 1.0],
+                ..wall_tile(1)
+            },
+        ],
+        doors: Vec::new(),
+        switches: vec![Switch {
+            x: 7,
+            y: 4,
+            on: false,
+            off_tile: 10,
+            on_tile: 11,
+            links: vec![SwitchLink {
+                x: 5,
+                y: 5,
+                off_tile: 1,
+                on_tile: 0,
+            }],
+        }],
+        push_walls: Vec::new(),
+        portals: face_portals(4, 0, FACE_SOUTH, 0, 5, FACE_EAST),
+        sectors: Vec::new(),
+        fog: Fog {
+            mode: FOG_LINEAR,
+            colour: (30, 30, 36),
+            start: 160.0,
+            end: 640.0,
+        },
+        ambient: 1.0,
+        lights: Vec::new(),
     };
+    map1.doors = find_doors(&map1);
+    map1.push_walls = find_push_walls(&map1);
+    // push walls are put back on the original tiles when the game restarts.
+    let start_tiles = map1.wall_tiles.clone();
+    let mut map2 = sector_level();
 
     let mut player1 = Player {
         x: 300.0,
         y: 300.0,
         angle: 0.0,
+        velocity_x: 0.0,
+        velocity_y: 0.0,
+        turn_speed: 0.0,
+        pitch: 0.0,
+        z: EYE_HEIGHT,
+        velocity_z: 0.0,
+        eye_height: EYE_HEIGHT,
+        health: 100.0,
+        keys: [false; 3],
+        secrets: 0,
+        bob_phase: 0.0,
+        bob_amount: 0.0,
+        flashlight: false,
+        battery: 100.0,
     };
 
-    let mut sprite1 = Sprite {
-        npc_type: 1,
-        state: 1,
+    let mut settings = Settings {
+        head_bob: true,
+        head_bob_intensity: 1.0,
+        battery_drain: true,
+    };
+
+    let mut debug = Debug {
+        enabled: false,
+        noclip: false,
+        god_mode: false,
+        free_fly: false,
+    };
+
+    let movement = Movement {
+        acceleration: 0.002,
+        max_speed: 0.2,
+        friction: 0.001,
+        run_multiplier: 1.6,
+        turn_acceleration: 0.002,
+        max_turn_speed: 0.2,
+        turn_friction: 0.003,
+        look_speed: 0.05,
+        max_pitch: 30.0,
+        jump_speed: 0.25,
+        gravity: 0.001,
+        crouch_height: 16.0,
+        crouch_speed: 0.1,
+        crouch_multiplier: 0.5,
+        step_height: 20.0,
+    };
+
+    let mut sprite1 = Sprite {
+        npc_type: 1,
+        state: 1,
+        key: KEY_RED,
         map: 0,
         x: 2.0 * 64.0,
         y: 6.0 * 64.0,
         z: 20.0,
+        light: None,
     };
     let mut sprite2 = Sprite {
         npc_type: 2,
         state: 1,
+        key: -1,
         map: 1,
         x: 1.5 * 64.0,
         y: 4.5 * 64.0,
         z: 1.0,
+        light: Some(PointLight {
+            colour: [1.0, 0.75, 0.4],
+            radius: 192.0,
+            flicker: 0.3,
+        }),
     };
     let mut sprite3 = Sprite {
         npc_type: 2,
         state: 1,
+        key: -1,
         map: 1,
         x: 3.5 * 64.0,
         y: 4.5 * 64.0,
         z: 1.0,
+        light: Some(PointLight {
+            colour: [1.0, 0.75, 0.4],
+            radius: 192.0,
+            flicker: 0.3,
+        }),
     };
     let mut sprite4 = Sprite {
         npc_type: 3,
         state: 1,
+        key: -1,
         map: 2,
         x: 2.5 * 64.0,
         y: 2.0 * 64.0,
         z: 20.0,
+        light: None,
+    };
+    let mut sprite5 = Sprite {
+        npc_type: 1,
+        state: 1,
+        key: KEY_BLUE,
+        map: 0,
+        x: 4.5 * 64.0,
+        y: 1.5 * 64.0,
+        z: 20.0,
+        light: None,
     };
 
-    let mut depth: [i32; 120] = [0; 120];
+    let mut view = View {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        depth: [[0; 80]; 120],
+        direct: [0.0; 120],
+        mirrors: [None; 120],
+        god_mode: false,
+    };
 
     let mut keys = Keyboard {
         up: false,
         down: false,
         left: false,
         right: false,
+        run: false,
+        look_up: false,
+        look_down: false,
+        jump: false,
+        crouch: false,
     };
 
     let time = Instant::now();
@@ -146,6 +1424,9 @@ fn main() -> Result<(), String> {
     let mut frame2;
     let mut fps;
 
+    let mut message = String::new();
+    let mut message_timer: u128 = 0;
+
     let mut game_state = 0;
     let mut timer = 0;
     let mut fade = 0.0;
@@ -187,8 +1468,85 @@ fn main() -> Result<(), String> {
                     if keycode == Keycode::Down {
                         keys.down = true;
                     }
+                    if keycode == Keycode::LShift {
+                        keys.run = true;
+                    }
+                    if keycode == Keycode::PageUp {
+                        keys.look_up = true;
+                    }
+                    if keycode == Keycode::PageDown {
+                        keys.look_down = true;
+                    }
+                    if keycode == Keycode::Space {
+                        keys.jump = true;
+                    }
+                    if keycode == Keycode::LCtrl {
+                        keys.crouch = true;
+                    }
+                    if keycode == Keycode::F1 {
+                        debug.enabled = !debug.enabled;
+                    }
+                    if keycode == Keycode::F2 && debug.enabled {
+                        debug.noclip = !debug.noclip;
+                    }
+                    if keycode == Keycode::F3 && debug.enabled {
+                        debug.god_mode = !debug.god_mode;
+                    }
+                    if keycode == Keycode::F4 && debug.enabled {
+                        debug.free_fly = !debug.free_fly;
+                    }
+                    if keycode == Keycode::F5 && debug.enabled {
+                        // switch to the sector test level and back, both start at the same spot.
+                        std::mem::swap(&mut map1, &mut map2);
+                        player1.x = 300.0;
+                        player1.y = 300.0;
+                        player1.velocity_x = 0.0;
+                        player1.velocity_y = 0.0;
+                    }
+                    if keycode == Keycode::H {
+                        settings.head_bob = !settings.head_bob;
+                    }
+                    if keycode == Keycode::J {
+                        // cycle through gentler head bobs for motion sensitive players.
+                        if settings.head_bob_intensity > 0.75 {
+                            settings.head_bob_intensity = 0.25;
+                        } else if settings.head_bob_intensity > 0.375 {
+                            settings.head_bob_intensity = 1.0;
+                        } else {
+                            settings.head_bob_intensity = 0.5;
+                        }
+                        message = format!(
+                            "Head bob intensity: {}%",
+                            (settings.head_bob_intensity * 100.0) as i32
+                        );
+                        message_timer = 2000;
+                    }
+                    if keycode == Keycode::F {
+                        if player1.battery > 0.0 {
+                            player1.flashlight = !player1.flashlight;
+                        } else {
+                            message = String::from("The flashlight's battery is flat.");
+                            message_timer = 2000;
+                        }
+                    }
+                    if keycode == Keycode::B {
+                        settings.battery_drain = !settings.battery_drain;
+                    }
                     if keycode == Keycode::E {
-                        door_open(&player1, &mut map1, &mut sprite1);
+                        if let Some(text) = use_ray(
+                            &mut player1,
+                            &mut map1,
+                            &mut [
+                                &mut sprite1,
+                                &mut sprite2,
+                                &mut sprite3,
+                                &mut sprite4,
+                                &mut sprite5,
+                            ],
+                        ) {
+                            message = text;
+                            message_timer = 2000;
+                        }
                     }
                 }
                 Event::KeyUp {
@@ -207,6 +1565,21 @@ fn main() -> Result<(), String> {
                     if keycode == Keycode::Down {
                         keys.down = false;
                     }
+                    if keycode == Keycode::LShift {
+                        keys.run = false;
+                    }
+                    if keycode == Keycode::PageUp {
+                        keys.look_up = false;
+                    }
+                    if keycode == Keycode::PageDown {
+                        keys.look_down = false;
+                    }
+                    if keycode == Keycode::Space {
+                        keys.jump = false;
+                    }
+                    if keycode == Keycode::LCtrl {
+                        keys.crouch = false;
+                    }
                 }
                 _ => {}
             }
@@ -235,31 +1608,118 @@ fn main() -> Result<(), String> {
             player1.x = 300.0;
             player1.y = 300.0;
             player1.angle = 0.0;
+            player1.velocity_x = 0.0;
+            player1.velocity_y = 0.0;
+            player1.turn_speed = 0.0;
+            player1.pitch = 0.0;
+            player1.z = EYE_HEIGHT;
+            player1.velocity_z = 0.0;
+            player1.eye_height = EYE_HEIGHT;
+            player1.health = 100.0;
+            player1.keys = [false; 3];
+            player1.secrets = 0;
+            player1.bob_phase = 0.0;
+            player1.bob_amount = 0.0;
+            player1.flashlight = false;
+            player1.battery = 100.0;
             sprite4.x = 2.5 * 64.0;
             sprite4.y = 2.0 * 64.0;
             sprite1.state = 1;
-            map1.wall_tiles[19] = 4;
-            map1.wall_tiles[26] = 4;
+            sprite5.state = 1;
+            for door in map1.doors.iter_mut() {
+                door.open = 0.0;
+                door.state = DOOR_CLOSED;
+                door.timer = 0;
+            }
+            for i in 0..map1.switches.len() {
+                map1.switches[i].on = false;
+                map1.apply_switch(i);
+            }
+            for i in 0..map1.push_walls.len() {
+                let push_wall = &map1.push_walls[i];
+                let tile = map1.tile_id(push_wall.x, push_wall.y);
+                let from = (push_wall.y * map1.width + push_wall.x) as usize;
+                let to = (push_wall.start_y * map1.width + push_wall.start_x) as usize;
+                map1.wall_tiles[from] = start_tiles[from];
+                map1.wall_tiles[to] = tile;
+
+                let push_wall = &mut map1.push_walls[i];
+                push_wall.x = push_wall.start_x;
+                push_wall.y = push_wall.start_y;
+                push_wall.offset = 0.0;
+                push_wall.moved = 0;
+                push_wall.state = PUSH_READY;
+            }
         }
         if game_state == 2 {
-            keyboard_input(&keys, &mut player1, fps, &map1);
+            keyboard_input(&keys, &mut player1, &movement, &debug, fps, &map1);
+            if let Some(text) = touch_pickups(
+                &mut player1,
+                &mut [
+                    &mut sprite1,
+                    &mut sprite2,
+                    &mut sprite3,
+                    &mut sprite4,
+                    &mut sprite5,
+                ],
+            ) {
+                message = text;
+                message_timer = 2000;
+            }
+            if player1.flashlight && settings.battery_drain {
+                player1.battery -= FLASHLIGHT_DRAIN * fps as f32;
+                if player1.battery <= 0.0 {
+                    player1.battery = 0.0;
+                    player1.flashlight = false;
+                    message = String::from("The flashlight's battery is flat.");
+                    message_timer = 2000;
+                }
+            }
+            update_doors(
+                &mut map1,
+                &player1,
+                &[&sprite1, &sprite2, &sprite3, &sprite4, &sprite5],
+                fps,
+            );
+            update_push_walls(
+                &mut map1,
+                &player1,
+                &[&sprite1, &sprite2, &sprite3, &sprite4, &sprite5],
+                fps,
+            );
+            update_lights(
+                &mut map1,
+                &player1,
+                &[&sprite1, &sprite2, &sprite3, &sprite4, &sprite5],
+                frame2,
+            );
 
             canvas.set_draw_color(pixels::Color::RGB(0, 0, 0));
             canvas.clear();
 
             draw_sky(&player1, &mut canvas)?;
-            draw_rays(&player1, &map1, &mut canvas, &mut depth)?;
-
-            if (player1.x as i32 >> 6) == 1 && (player1.y as i32 >> 6) == 1 {
+            draw_rays(&player1, &map1, &settings, &mut canvas, &mut view)?;
+
+            let tile = map1.tile(player1.x as i32 >> 6, player1.y as i32 >> 6);
+            if tile.damage > 0.0 && !debug.god_mode() {
+                player1.health -= tile.damage * fps as f32 / 1000.0;
+                if player1.health <= 0.0 {
+                    fade = 0.0;
+                    timer = 0;
+                    game_state = 4;
+                }
+            }
+            if tile.trigger == TRIGGER_EXIT && !debug.free_fly() {
                 fade = 0.0;
                 timer = 0;
                 game_state = 3;
             }
 
+            view.god_mode = debug.god_mode();
             draw_sprite(
                 &mut sprite1,
                 &player1,
-                depth,
+                &view,
                 &mut game_state,
                 fps,
                 &mut map1,
@@ -268,7 +1728,7 @@ fn main() -> Result<(), String> {
             draw_sprite(
                 &mut sprite2,
                 &player1,
-                depth,
+                &view,
                 &mut game_state,
                 fps,
                 &mut map1,
@@ -277,7 +1737,7 @@ fn main() -> Result<(), String> {
             draw_sprite(
                 &mut sprite3,
                 &player1,
-                depth,
+                &view,
                 &mut game_state,
                 fps,
                 &mut map1,
@@ -286,12 +1746,26 @@ fn main() -> Result<(), String> {
             draw_sprite(
                 &mut sprite4,
                 &player1,
-                depth,
+                &view,
                 &mut game_state,
                 fps,
                 &mut map1,
                 &mut canvas,
             )?;
+            draw_sprite(
+                &mut sprite5,
+                &player1,
+                &view,
+                &mut game_state,
+                fps,
+                &mut map1,
+                &mut canvas,
+            )?;
+
+            if message_timer > 0 {
+                message_timer = message_timer.saturating_sub(fps);
+                draw_message(&message, &mut canvas)?;
+            }
         }
 
         if game_state == 3 {
@@ -305,407 +1779,1986 @@ fn main() -> Result<(), String> {
                 timer = 0;
                 game_state = 0;
             }
-        }
+        }
+
+        if game_state == 4 {
+            if fade < 1.0 {
+                fade += 0.0005 * (fps) as f32;
+            }
+            screen(3, fade, &mut canvas)?;
+            timer += 1 * fps;
+            if timer > 3000 {
+                fade = 0.0;
+                timer = 0;
+                game_state = 0;
+            }
+        }
+
+        canvas.present();
+    }
+
+    Ok(())
+}
+
+/// Uses the closest interactable thing along the player's view direction.
+///
+/// The ray stops at the first wall, door, switch or push wall within `USE_REACH`,
+/// sprites in front of it are used instead.
+fn use_ray(player: &mut Player, map: &mut Map, sprites: &mut [&mut Sprite]) -> Option<String> {
+    let cs = player.angle.cos();
+    let sn = player.angle.sin();
+
+    let mut reach = USE_REACH;
+    let mut door_cell = None;
+    let mut switch_index = None;
+    let mut push_index = None;
+    let mut distance = 0.0;
+    while distance < USE_REACH {
+        let mx = (player.x + cs * distance) as i32 >> 6;
+        let my = (player.y + sn * distance) as i32 >> 6;
+        if let Some(door) = map.door(mx, my) {
+            // the ray passes an open door, it is only closed if nothing else is in reach.
+            if door.state == DOOR_OPEN {
+                if door_cell.is_none() {
+                    door_cell = Some((mx, my));
+                }
+                distance += 4.0;
+                continue;
+            }
+            door_cell = Some((mx, my));
+            reach = distance;
+            break;
+        }
+        if map.switch_index(mx, my).is_some() {
+            switch_index = map.switch_index(mx, my);
+            reach = distance;
+            break;
+        }
+        if map.push_wall_index(mx, my).is_some() {
+            push_index = map.push_wall_index(mx, my);
+            reach = distance;
+            break;
+        }
+        if map.is_solid(mx, my)
+            || inside_pillar(map, player.x + cs * distance, player.y + sn * distance)
+            || crosses_diagonal(
+                map,
+                player.x,
+                player.y,
+                player.x + cs * distance,
+                player.y + sn * distance,
+            )
+        {
+            reach = distance;
+            break;
+        }
+        distance += 4.0;
+    }
+
+    let mut nearest = None;
+    for (i, sprite) in sprites.iter().enumerate() {
+        if !sprite.can_interact() {
+            continue;
+        }
+        let sx = sprite.x - player.x;
+        let sy = sprite.y - player.y;
+        let along = sx * cs + sy * sn;
+        let across = sy * cs - sx * sn;
+        if along > 0.0 && along < reach && across.abs() < 16.0 {
+            nearest = Some(i);
+            reach = along;
+        }
+    }
+
+    if let Some(i) = nearest {
+        return sprites[i].interact(player);
+    }
+    if let Some(i) = switch_index {
+        let text = map.switches[i].interact(player);
+        map.apply_switch(i);
+        return text;
+    }
+    if let Some(i) = push_index {
+        let push_wall = &map.push_walls[i];
+        let (dx, dy) = push_wall.push_direction(player);
+        // the block needs room to move.
+        if push_wall.can_interact() && map.takes_push_wall(push_wall.x + dx, push_wall.y + dy) {
+            return map.push_walls[i].interact(player);
+        }
+    }
+    if let Some((mx, my)) = door_cell {
+        if let Some(door) = map.door_mut(mx, my) {
+            return door.interact(player);
+        }
+    }
+    None
+}
+
+/// Picks up the items the player walks into, returns what was picked up.
+fn touch_pickups(player: &mut Player, sprites: &mut [&mut Sprite]) -> Option<String> {
+    let mut text = None;
+    for sprite in sprites.iter_mut() {
+        if player.x < (sprite.x + 30.0)
+            && player.x > (sprite.x - 30.0)
+            && player.y < (sprite.y + 30.0)
+            && player.y > (sprite.y - 30.0)
+            && sprite.npc_type == 1
+            && sprite.state == 1
+        {
+            text = sprite.interact(player).or(text);
+        }
+    }
+    text
+}
+
+/// Returns true if a body of the given radius overlaps the map cell.
+fn overlaps_cell(x: f32, y: f32, radius: f32, mx: i32, my: i32) -> bool {
+    let left = (mx * 64) as f32;
+    let top = (my * 64) as f32;
+    x + radius > left && x - radius < left + 64.0 && y + radius > top && y - radius < top + 64.0
+}
+
+/// Places the player's flashlight and the lights carried by the sprites for
+/// this frame, flickering with the `time` in milliseconds.
+fn update_lights(map: &mut Map, player: &Player, sprites: &[&Sprite], time: u128) {
+    map.lights.clear();
+    if player.flashlight {
+        // the beam grows weak as the battery runs out.
+        let strength = (player.battery / 20.0).min(1.0);
+        map.lights.push(Light {
+            x: player.x,
+            y: player.y,
+            z: player.z - 8.0,
+            colour: [1.2 * strength, 1.15 * strength, 1.0 * strength],
+            radius: 320.0,
+            angle: player.angle,
+            cone: 0.35,
+        });
+    }
+    for (i, sprite) in sprites.iter().enumerate() {
+        if sprite.state != 1 {
+            continue;
+        }
+        if let Some(light) = &sprite.light {
+            // two waves at odd speeds make an uneven flicker, different for every sprite.
+            let t = time as f32 / 1000.0 + i as f32 * 1.7;
+            let wave = ((t * 7.3).sin() + (t * 13.1).sin()) * 0.25 + 0.5;
+            let brightness = 1.0 - light.flicker * wave;
+            // the light sits at the top of the sprite.
+            let z = map.floor_height_at(sprite.x, sprite.y) + 32.0;
+            map.lights.push(Light {
+                x: sprite.x,
+                y: sprite.y,
+                z,
+                colour: light.colour.map(|c| c * brightness),
+                radius: light.radius,
+                angle: 0.0,
+                cone: 0.0,
+            });
+        }
+    }
+}
+
+/// Slides doors open and shut, closing them again once nobody stands in the way.
+fn update_doors(map: &mut Map, player: &Player, sprites: &[&Sprite], fps: u128) {
+    for door in map.doors.iter_mut() {
+        let mut blocked = overlaps_cell(player.x, player.y, 20.0, door.x, door.y);
+        for sprite in sprites {
+            if sprite.state == 1 && overlaps_cell(sprite.x, sprite.y, 15.0, door.x, door.y) {
+                blocked = true;
+            }
+        }
+
+        if door.state == DOOR_OPENING {
+            door.open += DOOR_SPEED * fps as f32;
+            if door.open >= 1.0 {
+                door.open = 1.0;
+                door.state = DOOR_OPEN;
+                door.timer = 0;
+            }
+        } else if door.state == DOOR_OPEN {
+            door.timer += fps;
+            if door.timer > DOOR_OPEN_TIME && !blocked {
+                door.state = DOOR_CLOSING;
+            }
+        } else if door.state == DOOR_CLOSING && blocked {
+            // never close a door on someone.
+            door.state = DOOR_OPENING;
+        } else if door.state == DOOR_CLOSING {
+            door.open -= DOOR_SPEED * fps as f32;
+            if door.open <= 0.0 {
+                door.open = 0.0;
+                door.state = DOOR_CLOSED;
+            }
+        }
+    }
+}
+
+/// Slides moving push walls on, one tile at a time.
+///
+/// A block stops after `PUSH_DISTANCE` tiles or in front of anything solid,
+/// the player or a sprite.
+fn update_push_walls(map: &mut Map, player: &Player, sprites: &[&Sprite], fps: u128) {
+    for i in 0..map.push_walls.len() {
+        if map.push_walls[i].state != PUSH_MOVING {
+            continue;
+        }
+        map.push_walls[i].offset += PUSH_SPEED * fps as f32;
+        if map.push_walls[i].offset < 64.0 {
+            continue;
+        }
+
+        // the block has fully entered the next cell.
+        let push_wall = &map.push_walls[i];
+        let tile = map.tile_id(push_wall.x, push_wall.y);
+        let next_x = push_wall.x + push_wall.dx;
+        let next_y = push_wall.y + push_wall.dy;
+        map.wall_tiles[(push_wall.y * map.width + push_wall.x) as usize] = 0;
+        map.wall_tiles[(next_y * map.width + next_x) as usize] = tile;
+
+        let push_wall = &mut map.push_walls[i];
+        push_wall.x = next_x;
+        push_wall.y = next_y;
+        push_wall.offset = 0.0;
+        push_wall.moved += 1;
+
+        push_wall.state = PUSH_DONE;
+
+        let after_x = next_x + push_wall.dx;
+        let after_y = next_y + push_wall.dy;
+        let mut blocked = push_wall.moved >= PUSH_DISTANCE
+            || overlaps_cell(player.x, player.y, 20.0, after_x, after_y);
+        for sprite in sprites {
+            if sprite.state == 1 && overlaps_cell(sprite.x, sprite.y, 15.0, after_x, after_y) {
+                blocked = true;
+            }
+        }
+        if !blocked && map.takes_push_wall(after_x, after_y) {
+            map.push_walls[i].state = PUSH_MOVING;
+        }
+    }
+}
+
+/// Returns true if the player fits in at a point and can step up onto its floor.
+fn can_enter(map: &Map, player: &Player, movement: &Movement, x: f32, y: f32) -> bool {
+    if !map.is_open(x, y) {
+        return false;
+    }
+    let feet = player.z - player.eye_height;
+    let floor = map.floor_height_at(x, y);
+    floor - feet <= movement.step_height
+        && map.ceiling_height_at(x, y) - floor.max(feet) >= player.eye_height + 4.0
+}
+
+fn keyboard_input(
+    keys: &Keyboard,
+    player: &mut Player,
+    movement: &Movement,
+    debug: &Debug,
+    fps: u128,
+    map: &Map,
+) {
+    let frame_time = fps as f32;
+
+    let mut max_speed = movement.max_speed;
+    if keys.run {
+        max_speed *= movement.run_multiplier;
+    }
+    if keys.crouch {
+        max_speed *= movement.crouch_multiplier;
+    }
+
+    let mut thrust = 0.0;
+    if keys.up {
+        // accelerate the player forward.
+        thrust += 1.0;
+    }
+    if keys.down {
+        // accelerate the player backward.
+        thrust -= 1.0;
+    }
+
+    player.velocity_x += player.angle.cos() * thrust * movement.acceleration * frame_time;
+    player.velocity_y += player.angle.sin() * thrust * movement.acceleration * frame_time;
+
+    // friction takes the same amount off the speed whichever way the player moves.
+    let speed =
+        (player.velocity_x * player.velocity_x + player.velocity_y * player.velocity_y).sqrt();
+    let mut new_speed = speed - movement.friction * frame_time;
+    if new_speed < 0.0 {
+        new_speed = 0.0;
+    }
+    if new_speed > max_speed {
+        new_speed = max_speed;
+    }
+    if speed > 0.0 {
+        player.velocity_x *= new_speed / speed;
+        player.velocity_y *= new_speed / speed;
+    }
+
+    let x_offset;
+    if player.velocity_x < 0.0 {
+        x_offset = -20;
+    } else {
+        x_offset = 20;
+    }
+
+    let y_offset;
+    if player.velocity_y < 0.0 {
+        y_offset = -20;
+    } else {
+        y_offset = 20;
+    }
+
+    // move the player, stopping dead against walls.
+    let ahead_x = player.x + x_offset as f32;
+    let ahead_y = player.y + y_offset as f32;
+    let from_x = player.x as i32 >> 6;
+    let from_y = player.y as i32 >> 6;
+
+    // walls with a portal on the face ahead can be walked into, see below.
+    let mut x_face = FACE_EAST;
+    if x_offset > 0 {
+        x_face = FACE_WEST;
+    }
+    let mut y_face = FACE_SOUTH;
+    if y_offset > 0 {
+        y_face = FACE_NORTH;
+    }
+    // only the axis stepping across a portal face skips the collision tests.
+    let x_portal = (ahead_x as i32 >> 6) != from_x
+        && map.portal_index(ahead_x as i32 >> 6, from_y, x_face) >= 0;
+
+    if debug.noclip()
+        || x_portal
+        || (can_enter(map, player, movement, ahead_x, player.y)
+            && !map.crosses_wall(player.x, player.y, ahead_x, player.y)
+            && !crosses_diagonal(map, player.x, player.y, ahead_x, player.y)
+            && !inside_pillar(map, ahead_x, player.y))
+    {
+        player.x += player.velocity_x * frame_time;
+    } else {
+        player.velocity_x = 0.0;
+    }
+
+    let y_portal = (ahead_y as i32 >> 6) != from_y
+        && map.portal_index(player.x as i32 >> 6, ahead_y as i32 >> 6, y_face) >= 0;
+    if debug.noclip()
+        || y_portal
+        || (can_enter(map, player, movement, player.x, ahead_y)
+            && !map.crosses_wall(player.x, player.y, player.x, ahead_y)
+            && !crosses_diagonal(map, player.x, player.y, player.x, ahead_y)
+            && !inside_pillar(map, player.x, ahead_y))
+    {
+        player.y += player.velocity_y * frame_time;
+    } else {
+        player.velocity_y = 0.0;
+    }
+
+    // walking into a portal face comes out of the linked face. The x step is
+    // taken first, so a changed row means the cell was entered across its
+    // north or south face.
+    let mx = player.x as i32 >> 6;
+    let my = player.y as i32 >> 6;
+    let mut portal = -1;
+    if my > from_y {
+        portal = map.portal_index(mx, my, FACE_NORTH);
+    } else if my < from_y {
+        portal = map.portal_index(mx, my, FACE_SOUTH);
+    } else if mx > from_x {
+        portal = map.portal_index(mx, my, FACE_WEST);
+    } else if mx < from_x {
+        portal = map.portal_index(mx, my, FACE_EAST);
+    }
+    if portal >= 0 {
+        let portal = &map.portals[portal as usize];
+        let turn = portal.turn();
+        (player.x, player.y) = portal.carry(player.x, player.y);
+        player.angle += turn;
+        let velocity_x = player.velocity_x;
+        player.velocity_x = velocity_x * turn.cos() - player.velocity_y * turn.sin();
+        player.velocity_y = velocity_x * turn.sin() + player.velocity_y * turn.cos();
+    }
+
+    if debug.noclip() && map.sectors.is_empty() {
+        // stay inside the outer walls so the rays always hit something.
+        let max_x = ((map.width - 1) * 64) as f32 - 1.0;
+        let max_y = ((map.height - 1) * 64) as f32 - 1.0;
+        if player.x < 64.0 {
+            player.x = 64.0;
+        }
+        if player.x > max_x {
+            player.x = max_x;
+        }
+        if player.y < 64.0 {
+            player.y = 64.0;
+        }
+        if player.y > max_y {
+            player.y = max_y;
+        }
+    }
+
+    let mut turn = 0.0;
+    if keys.left {
+        // turn the player to the left.
+        turn -= 1.0;
+    }
+    if keys.right {
+        // turn the player to the right.
+        turn += 1.0;
+    }
+
+    if turn == 0.0 {
+        let slow_down = movement.turn_friction * frame_time;
+        if player.turn_speed > slow_down {
+            player.turn_speed -= slow_down;
+        } else if player.turn_speed < -slow_down {
+            player.turn_speed += slow_down;
+        } else {
+            player.turn_speed = 0.0;
+        }
+    } else {
+        player.turn_speed += turn * movement.turn_acceleration * frame_time;
+    }
+
+    if player.turn_speed > movement.max_turn_speed {
+        player.turn_speed = movement.max_turn_speed;
+    }
+    if player.turn_speed < -movement.max_turn_speed {
+        player.turn_speed = -movement.max_turn_speed;
+    }
+
+    player.angle += ((player.turn_speed * frame_time) * PI) / 180.0;
+    if player.angle < 0.0 {
+        player.angle += 2.0 * PI;
+    }
+    if player.angle > 2.0 * PI {
+        player.angle -= 2.0 * PI;
+    }
+
+    if keys.look_up {
+        // tilt the view up by moving the horizon down.
+        player.pitch += movement.look_speed * frame_time;
+    }
+    if keys.look_down {
+        // tilt the view down by moving the horizon up.
+        player.pitch -= movement.look_speed * frame_time;
+    }
+    if player.pitch > movement.max_pitch {
+        player.pitch = movement.max_pitch;
+    }
+    if player.pitch < -movement.max_pitch {
+        player.pitch = -movement.max_pitch;
+    }
+
+    // the camera sits eye_height above the player's feet.
+    let mut feet = player.z - player.eye_height;
+    let ground = map.floor_height_at(player.x, player.y);
+    let ceiling = map.ceiling_height_at(player.x, player.y);
+
+    if debug.free_fly() {
+        // fly up and down with the jump and crouch keys.
+        if keys.jump {
+            feet += max_speed * frame_time;
+        }
+        if keys.crouch {
+            feet -= max_speed * frame_time;
+        }
+        if feet < ground {
+            feet = ground;
+        }
+        player.velocity_z = 0.0;
+    } else {
+        let mut target_height = EYE_HEIGHT;
+        if keys.crouch {
+            target_height = movement.crouch_height;
+        }
+        if player.eye_height < target_height {
+            player.eye_height += movement.crouch_speed * frame_time;
+            if player.eye_height > target_height {
+                player.eye_height = target_height;
+            }
+        }
+        if player.eye_height > target_height {
+            player.eye_height -= movement.crouch_speed * frame_time;
+            if player.eye_height < target_height {
+                player.eye_height = target_height;
+            }
+        }
+
+        if keys.jump && feet <= ground {
+            // jump off the ground.
+            player.velocity_z = movement.jump_speed;
+        }
+
+        player.velocity_z -= movement.gravity * frame_time;
+        feet += player.velocity_z * frame_time;
+        // land on the floor, or step up onto a higher one.
+        if feet < ground {
+            feet = ground;
+            player.velocity_z = 0.0;
+        }
+    }
+
+    player.z = feet + player.eye_height;
+
+    // don't let the camera go through the ceiling.
+    if player.z > ceiling - 4.0 {
+        player.z = ceiling - 4.0;
+        player.velocity_z = 0.0;
+    }
+
+    // the head bob follows the walking speed and settles down smoothly when stopping.
+    let speed =
+        (player.velocity_x * player.velocity_x + player.velocity_y * player.velocity_y).sqrt();
+    let mut bob_target = speed / movement.max_speed;
+    if feet > 0.0 {
+        bob_target = 0.0;
+    }
+    let mut bob_blend = 0.01 * frame_time;
+    if bob_blend > 1.0 {
+        bob_blend = 1.0;
+    }
+    player.bob_amount += (bob_target - player.bob_amount) * bob_blend;
+    player.bob_phase += speed * frame_time * 0.1;
+    if player.bob_phase > 4.0 * PI {
+        player.bob_phase -= 4.0 * PI;
+    }
+}
+
+/// Returns the vertical camera offset and the sideways sway of the head bob.
+fn head_bob(player: &Player, settings: &Settings) -> (f32, f32) {
+    if !settings.head_bob {
+        return (0.0, 0.0);
+    }
+    let strength = player.bob_amount * settings.head_bob_intensity;
+    let bob_z = player.bob_phase.sin() * 2.0 * strength;
+    let sway = (player.bob_phase * 0.5).cos() * 1.5 * strength;
+    (bob_z, sway)
+}
+
+fn draw_sky(
+    player: &Player,
+    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
+) -> Result<(), String> {
+    let horizon = 40 + player.pitch as i32;
+    for y in 0..horizon {
+        // the sky band moves with the horizon when looking up and down.
+        let sky_y = (y - player.pitch as i32).clamp(0, 79);
+        for x in 0..120 {
+            let mut x_offset = ((player.angle * (180.0 / PI)) * 2.0) as i32 + x;
+            if x_offset < 0 {
+                x_offset += 120;
+            }
+
+            x_offset = x_offset % 120;
+            let pixel = ((sky_y * 120 + x_offset) * 3) as usize;
+            let red = SKY_DATA[pixel + 0];
+            let green = SKY_DATA[pixel + 1];
+            let blue = SKY_DATA[pixel + 2];
+            canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
+            //canvas.draw_point(Point::new(x as i32 * 8, y as i32 * 8))?;
+            canvas.fill_rect(Rect::new(120 + (x as i32 * 6), 80 + (y as i32 * 6), 6, 6))?;
+        }
+    }
+    Ok(())
+}
+
+/// Prints a line of text centred at the bottom of the view.
+fn draw_message(
+    text: &str,
+    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
+) -> Result<(), String> {
+    let x = 120 + 360 - (text.len() as i32 * 8) / 2;
+    canvas.string(x as i16, 540, text, pixels::Color::RGB(255, 255, 255))
+}
+
+fn screen(
+    screen_number: i32,
+    fade: f32,
+    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
+) -> Result<(), String> {
+    for y in 0..80 {
+        for x in 0..120 {
+            let pixel = (((y * 120) + x) * 3) as usize;
+            if screen_number == 1 {
+                let red = (TITLE[pixel + 0] as f32 * fade) as u8;
+                let green = (TITLE[pixel + 1] as f32 * fade) as u8;
+                let blue = (TITLE[pixel + 2] as f32 * fade) as u8;
+                canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
+                canvas.fill_rect(Rect::new(120 + (x as i32 * 6), 80 + (y as i32 * 6), 6, 6))?;
+            }
+            if screen_number == 2 {
+                let red = (WON[pixel + 0] as f32 * fade) as u8;
+                let green = (WON[pixel + 1] as f32 * fade) as u8;
+                let blue = (WON[pixel + 2] as f32 * fade) as u8;
+                canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
+                canvas.fill_rect(Rect::new(120 + (x as i32 * 6), 80 + (y as i32 * 6), 6, 6))?;
+            }
+            if screen_number == 3 {
+                let red = (LOST[pixel + 0] as f32 * fade) as u8;
+                let green = (LOST[pixel + 1] as f32 * fade) as u8;
+                let blue = (LOST[pixel + 2] as f32 * fade) as u8;
+                canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
+                canvas.fill_rect(Rect::new(120 + (x as i32 * 6), 80 + (y as i32 * 6), 6, 6))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A wall, door or fence slice found by a ray.
+///
+/// `exit` is the distance at which the ray leaves the wall's cell again, the
+/// top of a low wall is seen between `distance` and `exit`. The wall stands
+/// on the floor of its cell at `bottom`. `portal` is the index of the face
+/// portal that was hit in `Map::portals`, or -1, `mirror` the face of a
+/// mirror wall that was hit, or -1. Walls seen in a mirror are drawn in its `tint`.
+/// The wall is lit at (`x`, `y`), where the ray meets it or just in front of it.
+struct WallHit {
+    x: f32,
+    y: f32,
+    distance: f32,
+    exit: f32,
+    bottom: f32,
+    height: f32,
+    texture: i32,
+    texture_x: f32,
+    shade: f32,
+    see_through: bool,
+    portal: i32,
+    mirror: i32,
+    tint: [f32; 3],
+}
+
+/// Returns the distance along a ray to where it leaves a map cell.
+fn cell_exit(start_x: f32, start_y: f32, ray_angle: f32, mx: i32, my: i32) -> f32 {
+    let cs = ray_angle.cos();
+    let sn = ray_angle.sin();
+    let mut exit_x = 1000000.0;
+    if cs > 0.0 {
+        exit_x = ((mx + 1) * 64) as f32 - start_x;
+        exit_x /= cs;
+    } else if cs < 0.0 {
+        exit_x = (mx * 64) as f32 - start_x;
+        exit_x /= cs;
+    }
+    let mut exit_y = 1000000.0;
+    if sn > 0.0 {
+        exit_y = ((my + 1) * 64) as f32 - start_y;
+        exit_y /= sn;
+    } else if sn < 0.0 {
+        exit_y = (my * 64) as f32 - start_y;
+        exit_y /= sn;
+    }
+    exit_x.min(exit_y)
+}
+
+/// A map cell crossed by a ray, between the distances `enter` and `exit`.
+struct CellSpan {
+    x: i32,
+    y: i32,
+    enter: f32,
+    exit: f32,
+}
+
+/// Lists the cells a ray passes through, nearest first, up to `max_distance`.
+fn cast_cells(
+    map: &Map,
+    start_x: f32,
+    start_y: f32,
+    ray_angle: f32,
+    max_distance: f32,
+) -> Vec<CellSpan> {
+    let mut spans = Vec::new();
+    let mut mx = (start_x / 64.0).floor() as i32;
+    let mut my = (start_y / 64.0).floor() as i32;
+    let mut enter = 0.0;
+    while enter <= max_distance && mx >= 0 && my >= 0 && mx < map.width && my < map.height {
+        let exit = cell_exit(start_x, start_y, ray_angle, mx, my);
+        spans.push(CellSpan {
+            x: mx,
+            y: my,
+            enter,
+            exit,
+        });
+
+        // step just over the border into the next cell.
+        mx = ((start_x + ray_angle.cos() * (exit + 0.01)) / 64.0).floor() as i32;
+        my = ((start_y + ray_angle.sin() * (exit + 0.01)) / 64.0).floor() as i32;
+        enter = exit;
+    }
+    spans
+}
+
+/// A stretch of a ray over one floor and ceiling, between `enter` and `exit`.
+///
+/// `border_u` is the position along the border the ray came in through, it
+/// lines up the texture of a step at that border. The point at distance d of
+/// the span is `origin` plus d in the direction `angle`, this differs from
+/// the view once the ray went through a portal or a mirror, which also
+/// gives it a `tint`.
+struct FloorSpan {
+    enter: f32,
+    exit: f32,
+    border_u: f32,
+    origin_x: f32,
+    origin_y: f32,
+    angle: f32,
+    tint: [f32; 3],
+    floor_height: f32,
+    ceiling_height: f32,
+    floor_texture: i32,
+    ceiling_texture: i32,
+}
+
+/// Looks up the floor and ceiling of the cells a ray passes through.
+fn floor_spans(
+    map: &Map,
+    cells: &[CellSpan],
+    start_x: f32,
+    start_y: f32,
+    ray_angle: f32,
+) -> Vec<FloorSpan> {
+    let mut spans = Vec::new();
+    for i in 0..cells.len() {
+        let cell = &cells[i];
+
+        // rays coming in from the west or the east cross a border along the y axis.
+        let mut border_u = start_x + ray_angle.cos() * cell.enter;
+        if i > 0 && cells[i - 1].x != cell.x {
+            border_u = start_y + ray_angle.sin() * cell.enter;
+        }
+        spans.push(FloorSpan {
+            enter: cell.enter,
+            exit: cell.exit,
+            border_u,
+            origin_x: start_x,
+            origin_y: start_y,
+            angle: ray_angle,
+            tint: [1.0; 3],
+            floor_height: map.floor_height(cell.x, cell.y),
+            ceiling_height: map.ceiling_height(cell.x, cell.y),
+            floor_texture: map.floor_texture(cell.x, cell.y),
+            ceiling_texture: map.ceiling_texture(cell.x, cell.y),
+        });
+    }
+    spans
+}
+
+/// The mirror a screen column looks into, used to draw sprites in it.
+#[derive(Clone, Copy, PartialEq)]
+struct MirrorView {
+    vertical: bool,
+    position: f32,
+    tint: [f32; 3],
+}
+
+impl MirrorView {
+    /// Reflects a point to where it is seen in the mirror.
+    fn reflect(&self, x: f32, y: f32) -> (f32, f32) {
+        if self.vertical {
+            return (2.0 * self.position - x, y);
+        }
+        (x, 2.0 * self.position - y)
+    }
+}
+
+/// Returns the face as a mirror face if the tile is a mirror, -1 otherwise.
+fn mirror_face(tile: &TileDef, face: usize) -> i32 {
+    if tile.mirror {
+        return face as i32;
+    }
+    -1
+}
+
+/// Follows a ray through the grid of a map, and on through any face portals
+/// and mirrors.
+///
+/// Returns the floor spans of the cells it passes, nearest first, the walls of
+/// the `view`'s column it hits, how far it goes before the first portal or
+/// mirror, and the first mirror if it is seen directly. Distances add up along
+/// the whole way. After `PORTAL_LIMIT` portals or `MIRROR_LIMIT` bounces the
+/// ray stops and shows the portal's or the mirror's wall.
+fn cast_grid(
+    map: &Map,
+    start_x: f32,
+    start_y: f32,
+    ray_angle: f32,
+    view: &ColumnView,
+) -> (Vec<FloorSpan>, Vec<WallHit>, f32, Option<MirrorView>) {
+    let mut spans = Vec::new();
+    let mut hits = Vec::new();
+    let mut direct = 1000000.0;
+    let mut first_mirror = None;
+    let mut x = start_x;
+    let mut y = start_y;
+    let mut angle = ray_angle;
+    let mut travelled = 0.0;
+    let mut tint = [1.0; 3];
+    let mut portals_passed = 0;
+    let mut bounces = 0;
+
+    loop {
+        let part_view = ColumnView { travelled, ..*view };
+        let mut part_hits = cast_ray(map, x, y, angle, &part_view);
+
+        // the ray goes on at the first portal or mirror, walls behind it are not seen.
+        let turn = part_hits.iter().position(|hit| {
+            (hit.portal >= 0 && portals_passed < PORTAL_LIMIT)
+                || (hit.mirror >= 0 && bounces < MIRROR_LIMIT)
+        });
+        if let Some(i) = turn {
+            part_hits.truncate(i + 1);
+        }
+
+        // floors and ceilings are only drawn up to the farthest wall.
+        let mut far_distance = 1000000.0;
+        if let Some(hit) = part_hits.last() {
+            far_distance = hit.distance;
+        }
+        let cells = cast_cells(map, x, y, angle, far_distance);
+        for mut span in floor_spans(map, &cells, x, y, angle) {
+            span.enter += travelled;
+            span.exit += travelled;
+            span.origin_x -= angle.cos() * travelled;
+            span.origin_y -= angle.sin() * travelled;
+            span.tint = tint;
+            spans.push(span);
+        }
+
+        let mut portal = -1;
+        let mut mirror = -1;
+        let mut end = None;
+        if turn.is_some() {
+            end = part_hits.pop();
+        }
+        if let Some(hit) = &end {
+            if portals_passed < PORTAL_LIMIT {
+                portal = hit.portal;
+            }
+            if bounces < MIRROR_LIMIT {
+                mirror = hit.mirror;
+            }
+        }
+        for mut hit in part_hits {
+            hit.distance += travelled;
+            hit.exit += travelled;
+            hit.tint = tint;
+            hits.push(hit);
+        }
+        let hit;
+        if let Some(end) = end {
+            hit = end;
+        } else {
+            break;
+        }
+
+        if portals_passed == 0 && bounces == 0 {
+            direct = hit.distance;
+        }
+        let hit_x = x + angle.cos() * hit.distance;
+        let hit_y = y + angle.sin() * hit.distance;
+        if portal >= 0 {
+            // carry on from the linked face, a little way out of its wall.
+            let portal = &map.portals[portal as usize];
+            (x, y) = portal.carry(hit_x, hit_y);
+            angle += portal.turn();
+            portals_passed += 1;
+        } else {
+            let face = mirror as usize;
+            let vertical = face == FACE_EAST || face == FACE_WEST;
+            if portals_passed == 0 && bounces == 0 {
+                let mut position = (hit_y / 64.0).round() * 64.0;
+                if vertical {
+                    position = (hit_x / 64.0).round() * 64.0;
+                }
+                first_mirror = Some(MirrorView {
+                    vertical,
+                    position,
+                    tint: [1.0; 3],
+                });
+            }
+
+            // bounce off the mirror, it tints everything seen in it.
+            let normal = face_angle(face);
+            let mirror_tile = map.tile(
+                (hit_x - normal.cos() * 0.5) as i32 >> 6,
+                (hit_y - normal.sin() * 0.5) as i32 >> 6,
+            );
+            for (channel, mirror_channel) in tint.iter_mut().zip(mirror_tile.tint) {
+                *channel *= mirror_channel;
+            }
+            if let Some(first) = first_mirror.as_mut() {
+                if bounces == 0 {
+                    first.tint = tint;
+                }
+            }
+            if vertical {
+                angle = PI - angle;
+            } else {
+                angle = 2.0 * PI - angle;
+            }
+            x = hit_x;
+            y = hit_y;
+            bounces += 1;
+        }
+        if angle < 0.0 {
+            angle += 2.0 * PI;
+        }
+        if angle > 2.0 * PI {
+            angle -= 2.0 * PI;
+        }
+        x += angle.cos() * 0.01;
+        y += angle.sin() * 0.01;
+        travelled += hit.distance + 0.01;
+    }
+    (spans, hits, direct, first_mirror)
+}
+
+/// Follows a ray through the sectors of a map.
+///
+/// Returns the floor spans of the sectors it passes, nearest first, and the
+/// wall it ends on.
+fn cast_sectors(
+    map: &Map,
+    start_x: f32,
+    start_y: f32,
+    ray_angle: f32,
+) -> (Vec<FloorSpan>, Vec<WallHit>) {
+    let mut spans = Vec::new();
+    let mut hits = Vec::new();
+    let cs = ray_angle.cos();
+    let sn = ray_angle.sin();
+
+    let mut current = map.sector_at(start_x, start_y);
+    let mut enter = 0.0;
+    let mut border_u = 0.0;
+
+    // every sector is convex, so a ray passes through each one at most once.
+    for _ in 0..map.sectors.len() {
+        let sector;
+        if let Some(i) = current {
+            sector = &map.sectors[i];
+        } else {
+            break;
+        }
+
+        // find the wall the ray leaves the sector through.
+        let mut exit = 1000000.0;
+        let mut exit_wall = None;
+        let mut exit_u = 0.0;
+        for wall in sector.walls.iter() {
+            let segment = (wall.x0, wall.y0, wall.x1, wall.y1);
+            if let Some((t, u)) = segment_cross(start_x, start_y, cs, sn, segment) {
+                if t > enter + 0.01 && t < exit {
+                    exit = t;
+                    exit_wall = Some(wall);
+                    exit_u = u;
+                }
+            }
+        }
+
+        spans.push(FloorSpan {
+            enter,
+            exit,
+            border_u,
+            origin_x: start_x,
+            origin_y: start_y,
+            angle: ray_angle,
+            tint: [1.0; 3],
+            floor_height: sector.floor_height,
+            ceiling_height: sector.ceiling_height,
+            floor_texture: sector.floor_texture,
+            ceiling_texture: sector.ceiling_texture,
+        });
+
+        current = None;
+        if let Some(wall) = exit_wall {
+            let length = dist(wall.x0, wall.y0, wall.x1, wall.y1, ray_angle);
+            if wall.portal >= 0 {
+                current = Some(wall.portal as usize);
+                enter = exit;
+                border_u = exit_u * length;
+            } else {
+                // lit from a point just inside the sector, the edge itself may
+                // round to either side.
+                hits.push(WallHit {
+                    x: start_x + ray_angle.cos() * (exit - 0.5),
+                    y: start_y + ray_angle.sin() * (exit - 0.5),
+                    distance: exit,
+                    exit,
+                    bottom: sector.floor_height,
+                    height: sector.ceiling_height - sector.floor_height,
+                    texture: wall.texture,
+                    texture_x: texture_column(exit_u * length, false),
+                    // lit like the grid walls, brightest when running east to west.
+                    shade: 0.5 + 0.5 * ((wall.x1 - wall.x0) / length).abs(),
+                    see_through: false,
+                    portal: -1,
+                    mirror: -1,
+                    tint: [1.0; 3],
+                });
+            }
+        }
+    }
+    (spans, hits)
+}
+
+/// Converts a position along a wall face into a texture column.
+fn texture_column(u: f32, flip: bool) -> f32 {
+    let texture_x = (u / 2.0) % 32.0;
+    if flip {
+        return 31.0 - texture_x;
+    }
+    texture_x
+}
+
+/// The screen column a ray fills, seen from the height `z`.
+///
+/// Heights on the screen are kept as slopes, the height above the eye over
+/// the distance along the whole ray, which is `travelled` before this part of
+/// it. The column shows the slopes from `low` at its bottom to `high` at its top.
+#[derive(Clone, Copy)]
+struct ColumnView {
+    z: f32,
+    travelled: f32,
+    low: f32,
+    high: f32,
+}
+
+impl ColumnView {
+    /// Slope of the highest point of a wall, the near or the far edge of its top.
+    fn top_slope(&self, hit: &WallHit) -> f32 {
+        let top = hit.bottom + hit.height - self.z;
+        (top / (self.travelled + hit.distance)).max(top / (self.travelled + hit.exit))
+    }
+
+    /// Returns true if nothing beyond `distance` shows above the `covered` slope,
+    /// either the column is full or no wall of the map is high enough.
+    fn closed(&self, covered: f32, distance: f32, highest: f32) -> bool {
+        let rise = ((highest - self.z) / (self.travelled + distance)).max(0.0);
+        covered >= self.high || covered >= rise
+    }
+}
+
+/// Drops the walls hidden behind nearer ones from a sorted list of hits.
+///
+/// An opaque wall hides everything behind it up to its top, as the ground
+/// below it is solid. The list ends once the column is closed.
+fn visible_hits(hits: Vec<WallHit>, view: &ColumnView, highest: f32) -> Vec<WallHit> {
+    let mut covered = view.low;
+    let mut visible = Vec::new();
+    for hit in hits {
+        let top = view.top_slope(&hit);
+        if top <= covered {
+            continue;
+        }
+        let opaque = !hit.see_through;
+        let distance = hit.distance;
+        if opaque {
+            covered = top;
+        }
+        visible.push(hit);
+        if opaque && view.closed(covered, distance, highest) {
+            break;
+        }
+    }
+    visible
+}
+
+/// Follows a ray through the map and returns the walls it hits, nearest first.
+///
+/// The ray is stepped once along the horizontal and once along the vertical
+/// grid lines. Each pass stops once the opaque walls found close the `view`'s
+/// column, lower and see-through walls on the way are collected as well.
+/// Walls hidden by nearer ones are left out.
+fn cast_ray(
+    map: &Map,
+    start_x: f32,
+    start_y: f32,
+    ray_angle: f32,
+    view: &ColumnView,
+) -> Vec<WallHit> {
+    let mut mx;
+    let mut my;
+    let mut dof;
+    let mut ray_x: f32 = 0.0;
+    let mut ray_y: f32 = 0.0;
+    let mut x_offset: f32 = 0.0;
+    let mut y_offset: f32 = 0.0;
+    let max_dof = map.width.max(map.height);
+    let highest = map.highest_wall();
+    let mut covered = view.low;
+    let mut hits = Vec::new();
+
+    dof = 0;
+    let a_tan = -1.0 / (ray_angle.tan());
+
+    // looking up the map the ray hits the south side of a wall.
+    let horizontal_face = if ray_angle > PI {
+        FACE_SOUTH
+    } else {
+        FACE_NORTH
+    };
+
+    if ray_angle > PI {
+        ray_y = ((start_y as i32 >> 6) << 6) as f32 - 0.0001;
+        ray_x = (start_y - ray_y) * a_tan + start_x;
+        y_offset = -64.0;
+        x_offset = -y_offset * a_tan;
+    }
+
+    if ray_angle < PI {
+        ray_y = ((start_y as i32 >> 6) << 6) as f32 + 64.0;
+        ray_x = (start_y - ray_y) * a_tan + start_x;
+        y_offset = 64.0;
+        x_offset = -y_offset * a_tan;
+    }
+
+    if ray_angle == 0.0 || ray_angle == PI {
+        ray_x = start_x;
+        ray_y = start_y;
+        dof = max_dof;
+    }
+
+    while dof < max_dof {
+        mx = (ray_x as i32) >> 6;
+        my = (ray_y as i32) >> 6;
+
+        let tile = map.tile(mx, my);
+        let mut opaque_hit = false;
+        if map.moving_block(mx, my).is_some() || tile.diagonal != DIAGONAL_NONE || tile.radius > 0.0
+        {
+            // sliding push walls, diagonal walls and pillars are found separately below.
+        } else if map.door(mx, my).is_some() || tile.thin {
+            let mut slide = 0.0;
+            let mut horizontal_panel = panel_horizontal(map, mx, my);
+            if let Some(door) = map.door(mx, my) {
+                slide = door.open;
+                horizontal_panel = door.horizontal;
+            }
+            if horizontal_panel {
+                // doors and thin walls sit in the middle of the tile.
+                let panel_x = ray_x + x_offset / 2.0;
+                let panel_y = ray_y + y_offset / 2.0;
+                let u = panel_x - (mx * 64) as f32 - slide * 64.0;
+                if (panel_x as i32 >> 6) == mx && u >= 0.0 {
+                    let distance = dist(start_x, start_y, panel_x, panel_y, ray_angle);
+                    hits.push(WallHit {
+                        x: panel_x,
+                        y: panel_y,
+                        distance,
+                        exit: distance,
+                        bottom: map.floor_height(mx, my),
+                        height: tile.height,
+                        texture: tile.textures[horizontal_face],
+                        texture_x: texture_column(u, ray_angle < PI),
+                        shade: 1.0,
+                        see_through: tile.see_through,
+                        portal: -1,
+                        mirror: -1,
+                        tint: [1.0; 3],
+                    });
+                    opaque_hit = !tile.see_through;
+                }
+            }
+        } else if !tile.transparent {
+            let mut texture = tile.textures[horizontal_face];
 
-        if game_state == 4 {
-            if fade < 1.0 {
-                fade += 0.0005 * (fps) as f32;
+            // walls next to a door show the door frame.
+            let mut beside_y = my - 1;
+            if horizontal_face == FACE_SOUTH {
+                beside_y = my + 1;
             }
-            screen(3, fade, &mut canvas)?;
-            timer += 1 * fps;
-            if timer > 3000 {
-                fade = 0.0;
-                timer = 0;
-                game_state = 0;
+            if map.tile(mx, beside_y).jamb_texture >= 0 {
+                texture = map.tile(mx, beside_y).jamb_texture;
             }
+            hits.push(WallHit {
+                x: ray_x,
+                y: ray_y,
+                distance: dist(start_x, start_y, ray_x, ray_y, ray_angle),
+                exit: cell_exit(start_x, start_y, ray_angle, mx, my),
+                bottom: map.floor_height(mx, my),
+                height: tile.height,
+                texture,
+                texture_x: texture_column(ray_x, ray_angle < PI),
+                shade: 1.0,
+                see_through: tile.see_through,
+                portal: map.portal_index(mx, my, horizontal_face),
+                mirror: mirror_face(tile, horizontal_face),
+                tint: [1.0; 3],
+            });
+            opaque_hit = !tile.see_through;
         }
 
-        canvas.present();
-    }
-
-    Ok(())
-}
-
-fn door_open(player: &Player, map1: &mut Map, sprite: &mut Sprite) {
-    if sprite.state == 0 {
-        let x_offset;
-        if player.angle.cos() < 0.0 {
-            x_offset = -25;
-        } else {
-            x_offset = 25;
+        let mut closed = false;
+        if opaque_hit {
+            let hit = &hits[hits.len() - 1];
+            covered = covered.max(view.top_slope(hit));
+            closed = view.closed(covered, hit.distance, highest);
         }
-        let y_offset;
-        if player.angle.sin() < 0.0 {
-            y_offset = -25;
+        if closed {
+            dof = max_dof;
         } else {
-            y_offset = 25;
-        }
-        let ipx_add_xo = (player.x as i32 + x_offset) / 64;
-        let ipy_add_yo = (player.y as i32 + y_offset) / 64;
-        if map1.wall_tiles[(ipy_add_yo * map1.width + ipx_add_xo) as usize] == 4 {
-            map1.wall_tiles[(ipy_add_yo * map1.width + ipx_add_xo) as usize] = 0;
+            ray_x += x_offset;
+            ray_y += y_offset;
+
+            dof += 1;
         }
     }
-}
 
-fn keyboard_input(keys: &Keyboard, player: &mut Player, fps: u128, map: &Map) {
-    let x_offset;
-    if player.angle.cos() < 0.0 {
-        x_offset = -20;
+    dof = 0;
+    covered = view.low;
+    let negative_tan = -ray_angle.tan();
+
+    // looking left the ray hits the east side of a wall.
+    let vertical_face = if ray_angle > P2 && ray_angle < P3 {
+        FACE_EAST
     } else {
-        x_offset = 20;
+        FACE_WEST
+    };
+
+    if ray_angle > P2 && ray_angle < P3 {
+        ray_x = ((start_x as i32 >> 6) << 6) as f32 - 0.0001;
+        ray_y = (start_x - ray_x) * negative_tan + start_y;
+        x_offset = -64.0;
+        y_offset = -x_offset * negative_tan;
     }
 
-    let y_offset;
-    if player.angle.sin() < 0.0 {
-        y_offset = -20;
-    } else {
-        y_offset = 20;
+    if !(P2..=P3).contains(&ray_angle) {
+        ray_x = ((start_x as i32 >> 6) << 6) as f32 + 64.0;
+        ray_y = (start_x - ray_x) * negative_tan + start_y;
+        x_offset = 64.0;
+        y_offset = -x_offset * negative_tan;
     }
 
-    let ipx = player.x / 64.0;
-    let ipx_add_xo = (player.x as i32 + x_offset) / 64;
-    let ipx_sub_xo = (player.x as i32 - x_offset) / 64;
-    let ipy = player.y / 64.0;
-    let ipy_add_yo = (player.y as i32 + y_offset) / 64;
-    let ipy_sub_yo = (player.y as i32 - y_offset) / 64;
+    if ray_angle == 0.0 || ray_angle == PI {
+        ray_x = start_x;
+        ray_y = start_y;
+        dof = max_dof;
+    }
 
-    if keys.up == true {
-        // move the player forward.
+    while dof < max_dof {
+        mx = (ray_x as i32) >> 6;
+        my = (ray_y as i32) >> 6;
+
+        let tile = map.tile(mx, my);
+        let mut opaque_hit = false;
+        if map.moving_block(mx, my).is_some() || tile.diagonal != DIAGONAL_NONE || tile.radius > 0.0
+        {
+            // sliding push walls, diagonal walls and pillars are found separately below.
+        } else if map.door(mx, my).is_some() || tile.thin {
+            let mut slide = 0.0;
+            let mut horizontal_panel = panel_horizontal(map, mx, my);
+            if let Some(door) = map.door(mx, my) {
+                slide = door.open;
+                horizontal_panel = door.horizontal;
+            }
+            if !horizontal_panel {
+                let panel_x = ray_x + x_offset / 2.0;
+                let panel_y = ray_y + y_offset / 2.0;
+                let u = panel_y - (my * 64) as f32 - slide * 64.0;
+                if (panel_y as i32 >> 6) == my && u >= 0.0 {
+                    let distance = dist(start_x, start_y, panel_x, panel_y, ray_angle);
+                    hits.push(WallHit {
+                        x: panel_x,
+                        y: panel_y,
+                        distance,
+                        exit: distance,
+                        bottom: map.floor_height(mx, my),
+                        height: tile.height,
+                        texture: tile.textures[vertical_face],
+                        texture_x: texture_column(u, ray_angle > P2 && ray_angle < P3),
+                        shade: 0.5,
+                        see_through: tile.see_through,
+                        portal: -1,
+                        mirror: -1,
+                        tint: [1.0; 3],
+                    });
+                    opaque_hit = !tile.see_through;
+                }
+            }
+        } else if !tile.transparent {
+            let mut texture = tile.textures[vertical_face];
 
-        if map.wall_tiles[(ipy as i32 * map.width + ipx_add_xo) as usize] == 0 {
-            player.x += player.angle.cos() * 0.2 * fps as f32;
+            let mut beside_x = mx - 1;
+            if vertical_face == FACE_EAST {
+                beside_x = mx + 1;
+            }
+            if map.tile(beside_x, my).jamb_texture >= 0 {
+                texture = map.tile(beside_x, my).jamb_texture;
+            }
+            hits.push(WallHit {
+                x: ray_x,
+                y: ray_y,
+                distance: dist(start_x, start_y, ray_x, ray_y, ray_angle),
+                exit: cell_exit(start_x, start_y, ray_angle, mx, my),
+                bottom: map.floor_height(mx, my),
+                height: tile.height,
+                texture,
+                texture_x: texture_column(ray_y, ray_angle > P2 && ray_angle < P3),
+                shade: 0.5,
+                see_through: tile.see_through,
+                portal: map.portal_index(mx, my, vertical_face),
+                mirror: mirror_face(tile, vertical_face),
+                tint: [1.0; 3],
+            });
+            opaque_hit = !tile.see_through;
         }
 
-        if map.wall_tiles[(ipy_add_yo * map.width + ipx as i32) as usize] == 0 {
-            player.y += player.angle.sin() * 0.2 * fps as f32;
-        }
-    }
-    if keys.down == true {
-        // move the player backward.
-        if map.wall_tiles[(ipy as i32 * map.width + ipx_sub_xo) as usize] == 0 {
-            player.x -= player.angle.cos() * 0.2 * fps as f32;
+        let mut closed = false;
+        if opaque_hit {
+            let hit = &hits[hits.len() - 1];
+            covered = covered.max(view.top_slope(hit));
+            closed = view.closed(covered, hit.distance, highest);
         }
+        if closed {
+            dof = max_dof;
+        } else {
+            ray_x += x_offset;
+            ray_y += y_offset;
 
-        if map.wall_tiles[(ipy_sub_yo * map.width + ipx as i32) as usize] == 0 {
-            player.y -= player.angle.sin() * 0.2 * fps as f32;
+            dof += 1;
         }
     }
-    if keys.left == true {
-        // turn the player to the left.
-        player.angle -= ((0.2 * fps as f32) * PI) / 180.0;
-        if player.angle < 0.0 {
-            player.angle += 2.0 * PI;
+
+    for push_wall in map.push_walls.iter() {
+        if push_wall.state == PUSH_MOVING {
+            if let Some(hit) = push_wall_hit(map, push_wall, start_x, start_y, ray_angle) {
+                hits.push(hit);
+            }
         }
     }
-    if keys.right == true {
-        // turn the player to the right.
-        player.angle += ((0.2 * fps as f32) * PI) / 180.0;
-        if player.angle > 2.0 * PI {
-            player.angle -= 2.0 * PI;
+
+    hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    hits = visible_hits(hits, view, highest);
+
+    // diagonal walls and pillars in the cells up to there may hide it.
+    let mut far = 1000000.0;
+    if let Some(hit) = hits.last() {
+        far = hit.distance;
+    }
+    let mut found_shape = false;
+    for span in cast_cells(map, start_x, start_y, ray_angle, far) {
+        let mut shape_hit = diagonal_hit(map, span.x, span.y, start_x, start_y, ray_angle);
+        if shape_hit.is_none() {
+            shape_hit = pillar_hit(map, span.x, span.y, start_x, start_y, ray_angle);
+        }
+        if let Some(hit) = shape_hit {
+            if hit.distance < far {
+                hits.push(hit);
+                found_shape = true;
+            }
         }
     }
+    if found_shape {
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits = visible_hits(hits, view, highest);
+    }
+    hits
 }
 
-fn draw_sky(
-    player: &Player,
-    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
-) -> Result<(), String> {
-    for y in 0..40 {
-        for x in 0..120 {
-            let mut x_offset = ((player.angle * (180.0 / PI)) * 2.0) as i32 + x;
-            if x_offset < 0 {
-                x_offset += 120;
-            }
+/// Returns the end points of the diagonal wall in a cell, if it has one.
+fn diagonal_segment(map: &Map, mx: i32, my: i32) -> Option<(f32, f32, f32, f32)> {
+    let left = (mx * 64) as f32;
+    let top = (my * 64) as f32;
+    let diagonal = map.tile(mx, my).diagonal;
+    if diagonal == DIAGONAL_NE_SW {
+        return Some((left + 64.0, top, left, top + 64.0));
+    }
+    if diagonal == DIAGONAL_NW_SE {
+        return Some((left, top, left + 64.0, top + 64.0));
+    }
+    None
+}
 
-            x_offset = x_offset % 120;
-            let pixel = ((y * 120 + x_offset) * 3) as usize;
-            let red = SKY_DATA[pixel + 0];
-            let green = SKY_DATA[pixel + 1];
-            let blue = SKY_DATA[pixel + 2];
-            canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
-            //canvas.draw_point(Point::new(x as i32 * 8, y as i32 * 8))?;
-            canvas.fill_rect(Rect::new(120 + (x as i32 * 6), 80 + (y as i32 * 6), 6, 6))?;
-        }
+/// Intersects a line from (x, y) in the direction (dir_x, dir_y) with a segment.
+///
+/// Returns how many direction vectors along the line the crossing is, and how
+/// far along the segment from its first to its second end point (0.0 to 1.0).
+fn segment_cross(
+    x: f32,
+    y: f32,
+    dir_x: f32,
+    dir_y: f32,
+    segment: (f32, f32, f32, f32),
+) -> Option<(f32, f32)> {
+    let (x0, y0, x1, y1) = segment;
+    let seg_x = x1 - x0;
+    let seg_y = y1 - y0;
+    let denominator = dir_x * seg_y - dir_y * seg_x;
+    if denominator == 0.0 {
+        return None;
     }
-    Ok(())
+    let t = ((x0 - x) * seg_y - (y0 - y) * seg_x) / denominator;
+    let u = ((x0 - x) * dir_y - (y0 - y) * dir_x) / denominator;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    Some((t, u))
 }
 
-fn screen(
-    screen_number: i32,
-    fade: f32,
-    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
-) -> Result<(), String> {
-    for y in 0..80 {
-        for x in 0..120 {
-            let pixel = (((y * 120) + x) * 3) as usize;
-            if screen_number == 1 {
-                let red = (TITLE[pixel + 0] as f32 * fade) as u8;
-                let green = (TITLE[pixel + 1] as f32 * fade) as u8;
-                let blue = (TITLE[pixel + 2] as f32 * fade) as u8;
-                canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
-                canvas.fill_rect(Rect::new(120 + (x as i32 * 6), 80 + (y as i32 * 6), 6, 6))?;
-            }
-            if screen_number == 2 {
-                let red = (WON[pixel + 0] as f32 * fade) as u8;
-                let green = (WON[pixel + 1] as f32 * fade) as u8;
-                let blue = (WON[pixel + 2] as f32 * fade) as u8;
-                canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
-                canvas.fill_rect(Rect::new(120 + (x as i32 * 6), 80 + (y as i32 * 6), 6, 6))?;
-            }
-            if screen_number == 3 {
-                let red = (LOST[pixel + 0] as f32 * fade) as u8;
-                let green = (LOST[pixel + 1] as f32 * fade) as u8;
-                let blue = (LOST[pixel + 2] as f32 * fade) as u8;
-                canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
-                canvas.fill_rect(Rect::new(120 + (x as i32 * 6), 80 + (y as i32 * 6), 6, 6))?;
-            }
-        }
+/// Intersects a ray with the diagonal wall of a cell.
+fn diagonal_hit(
+    map: &Map,
+    mx: i32,
+    my: i32,
+    start_x: f32,
+    start_y: f32,
+    ray_angle: f32,
+) -> Option<WallHit> {
+    let segment = diagonal_segment(map, mx, my)?;
+    let (distance, u) = segment_cross(start_x, start_y, ray_angle.cos(), ray_angle.sin(), segment)?;
+    if distance <= 0.0 {
+        return None;
     }
-    Ok(())
+
+    // the side of the wall the ray comes from, north of the line or south.
+    let (x0, y0, x1, y1) = segment;
+    let line_y = y0 + (start_x - x0) * (y1 - y0) / (x1 - x0);
+    let north_side = start_y < line_y;
+    let tile = map.tile(mx, my);
+    let face = if north_side { FACE_NORTH } else { FACE_SOUTH };
+    Some(WallHit {
+        x: start_x + ray_angle.cos() * distance,
+        y: start_y + ray_angle.sin() * distance,
+        distance,
+        exit: distance,
+        bottom: map.floor_height(mx, my),
+        height: tile.height,
+        texture: tile.textures[face],
+        texture_x: texture_column(u * 64.0 * 2.0_f32.sqrt(), !north_side),
+        shade: 0.75,
+        see_through: tile.see_through,
+        portal: -1,
+        mirror: -1,
+        tint: [1.0; 3],
+    })
 }
 
-/// Cast the rays and draws the 3D view.
-///
-/// Raycasting algorithm is based on [Tutorial by 3DSage](https://youtu.be/gYRrGTC7GtA?list=PLMTDxt7L_MNXx7QP80seZUfcSoJ4jl34D&t=404).
+/// Intersects a ray with the round pillar of a cell.
 ///
-fn draw_rays(
-    player: &Player,
+/// The texture wraps around a whole number of times, about once every 64
+/// units of the circumference.
+fn pillar_hit(
     map: &Map,
-    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
-    depth: &mut [i32; 120],
-) -> Result<(), String> {
-    let mut mx;
-    let mut my;
-    let mut mp;
-    let mut dof;
-    let mut ray_x: f32 = 0.0;
-    let mut ray_y: f32 = 0.0;
-    let mut ray_angle: f32 = player.angle - DR * 30.0;
-    if ray_angle < 0.0 {
-        ray_angle += 2.0 * PI;
+    mx: i32,
+    my: i32,
+    start_x: f32,
+    start_y: f32,
+    ray_angle: f32,
+) -> Option<WallHit> {
+    let tile = map.tile(mx, my);
+    if tile.radius <= 0.0 {
+        return None;
     }
-    if ray_angle > 2.0 * PI {
-        ray_angle -= 2.0 * PI;
+    let center_x = (mx * 64) as f32 + 32.0;
+    let center_y = (my * 64) as f32 + 32.0;
+    let cs = ray_angle.cos();
+    let sn = ray_angle.sin();
+
+    // solve |start + t * direction - center| = radius for t.
+    let offset_x = start_x - center_x;
+    let offset_y = start_y - center_y;
+    let b = offset_x * cs + offset_y * sn;
+    let c = offset_x * offset_x + offset_y * offset_y - tile.radius * tile.radius;
+    let discriminant = b * b - c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let near = -b - discriminant.sqrt();
+    let far = -b + discriminant.sqrt();
+    if near <= 0.0 {
+        return None;
     }
-    let mut x_offset: f32 = 0.0;
-    let mut y_offset: f32 = 0.0;
-    let mut distance: f32 = 1.0;
-    for r in 0..120 {
-        let mut vmt = 0;
-        let mut hmt = 0;
-
-        dof = 0;
-        let mut distance_h = 1000000.0;
-        let mut horizontal_x = player.x;
-        let mut horizontal_y = player.y;
-        let a_tan = -1.0 / (ray_angle.tan());
-        if ray_angle > PI {
-            ray_y = ((player.y as i32 >> 6) << 6) as f32 - 0.0001;
-            ray_x = (player.y - ray_y) * a_tan + player.x;
-            y_offset = -64.0;
-            x_offset = -y_offset * a_tan;
-        }
-
-        if ray_angle < PI {
-            ray_y = ((player.y as i32 >> 6) << 6) as f32 + 64.0;
-            ray_x = (player.y - ray_y) * a_tan + player.x;
-            y_offset = 64.0;
-            x_offset = -y_offset * a_tan;
-        }
-
-        if ray_angle == 0.0 || ray_angle == PI {
-            ray_x = player.x;
-            ray_y = player.y;
-            dof = 8;
-        }
-
-        while dof < 8 {
-            mx = (ray_x as i32) >> 6;
-            my = (ray_y as i32) >> 6;
-
-            mp = my * map.width + mx;
-            if mp > 0 && mp < (map.width * map.height) && map.wall_tiles[mp as usize] > 0 {
-                hmt = map.wall_tiles[mp as usize] - 1;
-                horizontal_x = ray_x;
-                horizontal_y = ray_y;
-                distance_h = dist(player.x, player.y, horizontal_x, horizontal_y, ray_angle);
-                dof = 8;
-            } else {
-                ray_x += x_offset;
-                ray_y += y_offset;
 
-                dof += 1;
-            }
-        }
+    let normal_x = (start_x + cs * near - center_x) / tile.radius;
+    let normal_y = (start_y + sn * near - center_y) / tile.radius;
+    let mut around = normal_y.atan2(normal_x);
+    if around < 0.0 {
+        around += 2.0 * PI;
+    }
+    let face = if normal_y < 0.0 {
+        FACE_NORTH
+    } else {
+        FACE_SOUTH
+    };
+    // a whole number of texture widths fits around, so there is no seam.
+    let repeats = (2.0 * PI * tile.radius / 64.0).round().max(1.0);
+    let u = around / (2.0 * PI) * repeats * 64.0;
+    Some(WallHit {
+        x: start_x + cs * near,
+        y: start_y + sn * near,
+        distance: near,
+        exit: far,
+        bottom: map.floor_height(mx, my),
+        height: tile.height,
+        texture: tile.textures[face],
+        texture_x: texture_column(u, false),
+        // lit like the walls, brightest where the pillar faces north or south.
+        shade: 0.5 + 0.5 * normal_y.abs(),
+        see_through: tile.see_through,
+        portal: -1,
+        mirror: -1,
+        tint: [1.0; 3],
+    })
+}
 
-        dof = 0;
-        let mut distance_v = 1000000.0;
-        let mut vertical_x = player.x;
-        let mut vertical_y = player.y;
-        let negative_tan = -ray_angle.tan();
-        if ray_angle > P2 && ray_angle < P3 {
-            ray_x = ((player.x as i32 >> 6) << 6) as f32 - 0.0001;
-            ray_y = (player.x - ray_x) * negative_tan + player.y;
-            x_offset = -64.0;
-            y_offset = -x_offset * negative_tan;
-        }
+/// Returns true if a point lies inside the round pillar of its cell.
+fn inside_pillar(map: &Map, x: f32, y: f32) -> bool {
+    let mx = x as i32 >> 6;
+    let my = y as i32 >> 6;
+    let radius = map.tile(mx, my).radius;
+    if radius <= 0.0 {
+        return false;
+    }
+    dist(x, y, (mx * 64) as f32 + 32.0, (my * 64) as f32 + 32.0, 0.0) < radius
+}
 
-        if ray_angle < P2 || ray_angle > P3 {
-            ray_x = ((player.x as i32 >> 6) << 6) as f32 + 64.0;
-            ray_y = (player.x - ray_x) * negative_tan + player.y;
-            x_offset = 64.0;
-            y_offset = -x_offset * negative_tan;
-        }
+/// Returns true if a chasing sprite may move towards a point just ahead of it.
+fn chaser_can_step(map: &Map, sprite: &Sprite, x: f32, y: f32) -> bool {
+    map.is_open(x, y)
+        && !crosses_diagonal(map, sprite.x, sprite.y, x, y)
+        && !inside_pillar(map, x, y)
+}
 
-        if ray_angle == 0.0 || ray_angle == PI {
-            ray_x = player.x;
-            ray_y = player.y;
-            dof = 8;
+/// Returns true if moving from one point to another crosses a diagonal wall.
+fn crosses_diagonal(map: &Map, from_x: f32, from_y: f32, to_x: f32, to_y: f32) -> bool {
+    let cells = [
+        (from_x as i32 >> 6, from_y as i32 >> 6),
+        (to_x as i32 >> 6, to_y as i32 >> 6),
+    ];
+    for (mx, my) in cells {
+        if let Some(segment) = diagonal_segment(map, mx, my) {
+            if let Some((t, _)) =
+                segment_cross(from_x, from_y, to_x - from_x, to_y - from_y, segment)
+            {
+                if (0.0..=1.0).contains(&t) {
+                    return true;
+                }
+            }
         }
+    }
+    false
+}
 
-        while dof < 8 {
-            mx = (ray_x as i32) >> 6;
-            my = (ray_y as i32) >> 6;
+/// Intersects a ray with a sliding push wall block.
+fn push_wall_hit(
+    map: &Map,
+    push_wall: &PushWall,
+    start_x: f32,
+    start_y: f32,
+    ray_angle: f32,
+) -> Option<WallHit> {
+    let left = (push_wall.x * 64) as f32 + push_wall.dx as f32 * push_wall.offset;
+    let top = (push_wall.y * 64) as f32 + push_wall.dy as f32 * push_wall.offset;
+    let cs = ray_angle.cos();
+    let sn = ray_angle.sin();
+
+    // distances along the ray to the block's west/east and north/south sides.
+    let mut near_x = -1000000.0;
+    let mut far_x = 1000000.0;
+    if cs != 0.0 {
+        let a = (left - start_x) / cs;
+        let b = (left + 64.0 - start_x) / cs;
+        near_x = a.min(b);
+        far_x = a.max(b);
+    } else if start_x < left || start_x > left + 64.0 {
+        return None;
+    }
+    let mut near_y = -1000000.0;
+    let mut far_y = 1000000.0;
+    if sn != 0.0 {
+        let a = (top - start_y) / sn;
+        let b = (top + 64.0 - start_y) / sn;
+        near_y = a.min(b);
+        far_y = a.max(b);
+    } else if start_y < top || start_y > top + 64.0 {
+        return None;
+    }
 
-            mp = my * map.width + mx;
-            if mp > 0 && mp < (map.width * map.height) && map.wall_tiles[mp as usize] > 0 {
-                vmt = map.wall_tiles[mp as usize] - 1;
-                vertical_x = ray_x;
-                vertical_y = ray_y;
-                distance_v = dist(player.x, player.y, vertical_x, vertical_y, ray_angle);
-                dof = 8;
-            } else {
-                ray_x += x_offset;
-                ray_y += y_offset;
+    let near = near_x.max(near_y);
+    let far = far_x.min(far_y);
+    if near <= 0.0 || near > far {
+        return None;
+    }
 
-                dof += 1;
-            }
-        }
+    let tile = map.tile(push_wall.x, push_wall.y);
+    let hit_x = start_x + cs * near;
+    let hit_y = start_y + sn * near;
+    if near_x > near_y {
+        let face = if cs < 0.0 { FACE_EAST } else { FACE_WEST };
+        return Some(WallHit {
+            x: hit_x,
+            y: hit_y,
+            distance: near,
+            exit: far,
+            bottom: map.floor_height(push_wall.x, push_wall.y),
+            height: tile.height,
+            texture: tile.textures[face],
+            texture_x: texture_column(hit_y - top, cs < 0.0),
+            shade: 0.5,
+            see_through: false,
+            portal: -1,
+            mirror: -1,
+            tint: [1.0; 3],
+        });
+    }
 
-        let mut shade: f32 = 1.0;
+    let face = if sn > 0.0 { FACE_NORTH } else { FACE_SOUTH };
+    Some(WallHit {
+        x: hit_x,
+        y: hit_y,
+        distance: near,
+        exit: far,
+        bottom: map.floor_height(push_wall.x, push_wall.y),
+        height: tile.height,
+        texture: tile.textures[face],
+        texture_x: texture_column(hit_x - left, sn > 0.0),
+        shade: 1.0,
+        see_through: false,
+        portal: -1,
+        mirror: -1,
+        tint: [1.0; 3],
+    })
+}
 
-        if distance_v < distance_h {
-            hmt = vmt;
-            shade = 0.5;
-            ray_x = vertical_x;
-            ray_y = vertical_y;
-            distance = distance_v;
-        }
+/// Returns the screen row of a point at the given distance and height.
+fn screen_row(distance: f32, z: f32, horizon: i32, view_z: f32) -> i32 {
+    horizon - ((z - view_z) * 80.0 / distance.max(1.0)) as i32
+}
 
-        if distance_v > distance_h {
-            ray_x = horizontal_x;
-            ray_y = horizontal_y;
-            distance = distance_h;
-        }
+/// Returns the unclipped top row and height of a wall at the given distance.
+fn wall_span(distance: f32, bottom: f32, top: f32, horizon: i32, view_z: f32) -> (i32, i32) {
+    let line_offset = screen_row(distance, top, horizon, view_z);
+    let line_h = screen_row(distance, bottom, horizon, view_z) - line_offset;
+    (line_offset, line_h)
+}
 
-        let mut fixed_angle = player.angle - ray_angle;
-        if fixed_angle < 0.0 {
-            fixed_angle += 2.0 * PI;
-        }
+/// Draws one column of a wall and records its distance in the depth buffer.
+///
+/// Magenta texels of see-through walls are skipped.
+fn draw_wall_slice(
+    r: i32,
+    hit: &WallHit,
+    distance: f32,
+    camera: &Camera,
+    map: &Map,
+    depth: &mut [i32; 80],
+    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
+) -> Result<(), String> {
+    let top = hit.bottom + hit.height;
+    let (line_offset, line_h) = wall_span(distance, hit.bottom, top, camera.horizon, camera.z);
 
-        if fixed_angle > 2.0 * PI {
-            fixed_angle -= 2.0 * PI;
-        }
+    // textures repeat every TILE_SIZE, lined up with the floor.
+    let texture_y_step = hit.height / 2.0 / line_h as f32;
 
-        distance = distance * fixed_angle.cos();
+    // clip the wall slice to the view.
+    let mut wall_top = line_offset;
+    if wall_top < 0 {
+        wall_top = 0;
+    }
+    let mut wall_bottom = line_offset + line_h;
+    if wall_bottom > 80 {
+        wall_bottom = 80;
+    }
 
-        let mut line_h = ((TILE_SIZE * 80) as f32 / distance) as i32;
+    let mut texture_y: f32 =
+        (TILE_SIZE as f32 - top) / 2.0 + (wall_top - line_offset) as f32 * texture_y_step;
 
-        let texture_y_step = 32.0 / line_h as f32;
-        let mut texture_y_offset = 0.0;
+    for y in wall_top..wall_bottom {
+        let pixel = ((texture_y.rem_euclid(32.0) as usize) * 32 + (hit.texture_x) as usize) * 3
+            + (hit.texture as usize * 32 * 32 * 3);
+        texture_y += texture_y_step;
 
-        if line_h > 80 {
-            texture_y_offset = (line_h - 80) as f32 / 2.0;
-            line_h = 80;
+        if hit.see_through
+            && RGB_TEXTURES[pixel + 0] == 255
+            && RGB_TEXTURES[pixel + 1] == 0
+            && RGB_TEXTURES[pixel + 2] == 255
+        {
+            continue;
         }
 
-        let line_offset = 40 - (line_h >> 1);
-
-        depth[r as usize] = distance as i32;
+        // the height on the wall this row shows, for the lights.
+        let z = camera.z + (camera.horizon - y) as f32 * distance / 80.0;
+        let light = map.lighting(hit.x, hit.y, z);
+        let red = RGB_TEXTURES[pixel] as f32 * hit.shade * light[0] * hit.tint[0];
+        let green = RGB_TEXTURES[pixel + 1] as f32 * hit.shade * light[1] * hit.tint[1];
+        let blue = RGB_TEXTURES[pixel + 2] as f32 * hit.shade * light[2] * hit.tint[2];
+        canvas.set_draw_color(map.fog.blend(red, green, blue, distance));
+        //canvas.draw_point(Point::new(r * 8, y * 8))?;
+        canvas.fill_rect(Rect::new(120 + (r * 6), 80 + (y * 6), 6, 6))?;
+        depth[y as usize] = distance as i32;
+    }
+    Ok(())
+}
 
-        // Drawing walls
-        let mut texture_y: f32 = texture_y_offset * texture_y_step; //+ hmt as f32 * 32.0;
+/// Where the eye is for this frame, after the head bob, and how far each
+/// screen column sees. `direct` is how far a column looks before its ray
+/// turns at a portal or mirror, `mirrors` the mirror it looks into. In
+/// `god_mode` the enemies can't hurt the player.
+struct View {
+    x: f32,
+    y: f32,
+    z: f32,
+    depth: [[i32; 80]; 120],
+    direct: [f32; 120],
+    mirrors: [Option<MirrorView>; 120],
+    god_mode: bool,
+}
 
-        let mut texture_x: f32;
+/// The eye height and horizon row the walls of a column are drawn for.
+struct Camera {
+    z: f32,
+    horizon: i32,
+}
 
-        if shade == 1.0 {
-            texture_x = (ray_x / 2.0) % 32.0;
-            if ray_angle < PI {
-                texture_x = 31.0 - texture_x;
-            }
-        } else {
-            texture_x = (ray_y / 2.0) % 32.0;
-            if ray_angle > PI / 2.0 && ray_angle < (270.0 * PI) / 180.0 {
-                texture_x = 31.0 - texture_x;
-            }
-        }
+/// Cast the rays and draws the 3D view.
+///
+/// Raycasting algorithm is based on [Tutorial by 3DSage](https://youtu.be/gYRrGTC7GtA?list=PLMTDxt7L_MNXx7QP80seZUfcSoJ4jl34D&t=404).
+///
+fn draw_rays(
+    player: &Player,
+    map: &Map,
+    settings: &Settings,
+    canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
+    view: &mut View,
+) -> Result<(), String> {
+    let mut ray_angle: f32 = player.angle - DR * 30.0;
+    if ray_angle < 0.0 {
+        ray_angle += 2.0 * PI;
+    }
+    if ray_angle > 2.0 * PI {
+        ray_angle -= 2.0 * PI;
+    }
 
-        for y in 0..line_h {
-            let pixel = ((texture_y as usize) * 32 + (texture_x) as usize) * 3
-                + (hmt as usize * 32 * 32 * 3);
-            let red = (RGB_TEXTURES[pixel + 0] as f32 * shade) as u8;
-            let green = (RGB_TEXTURES[pixel + 1] as f32 * shade) as u8;
-            let blue = (RGB_TEXTURES[pixel + 2] as f32 * shade) as u8;
-            canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
-            //canvas.draw_point(Point::new(r * 8, (y + line_offset) * 8))?;
-            canvas.fill_rect(Rect::new(120 + (r * 6), 80 + ((y + line_offset) * 6), 6, 6))?;
+    // the head bob moves the camera up and down and sways it sideways.
+    let (bob_z, sway) = head_bob(player, settings);
+    let view_x = player.x - player.angle.sin() * sway;
+    let view_y = player.y + player.angle.cos() * sway;
+    let view_z = player.z + bob_z;
+    let horizon = 40 + player.pitch as i32;
+    view.x = view_x;
+    view.y = view_y;
+    view.z = view_z;
+    let depth = &mut view.depth;
+    let camera = Camera { z: view_z, horizon };
+    for r in 0..120 {
+        let mut ray_angle_fix = player.angle - ray_angle;
 
-            texture_y += texture_y_step;
+        if ray_angle_fix < 0.0 {
+            ray_angle_fix += 2.0 * PI;
         }
 
-        // Drawing floor
-        for y in (line_offset + line_h)..80 {
-            let delta_y = y as f32 - (80.0 / 2.0);
-            let degree = ray_angle;
+        if ray_angle_fix > (2.0 * PI) {
+            ray_angle_fix -= 2.0 * PI;
+        }
 
-            let mut ray_angle_fix = player.angle - ray_angle;
+        ray_angle_fix = ray_angle_fix.cos();
+
+        depth[r as usize] = [i32::MAX; 80];
+
+        let spans;
+        let hits;
+        if map.sectors.is_empty() {
+            // the rows of the column as slopes along the ray.
+            let column = ColumnView {
+                z: view_z,
+                travelled: 0.0,
+                low: ray_angle_fix * (horizon - 80) as f32 / 80.0,
+                high: ray_angle_fix * horizon as f32 / 80.0,
+            };
+            let direct;
+            (spans, hits, direct, view.mirrors[r as usize]) =
+                cast_grid(map, view_x, view_y, ray_angle, &column);
+            view.direct[r as usize] = direct * ray_angle_fix;
+        } else {
+            (spans, hits) = cast_sectors(map, view_x, view_y, ray_angle);
+            view.direct[r as usize] = 1000000.0;
+            view.mirrors[r as usize] = None;
+        }
 
-            if ray_angle_fix < 0.0 {
-                ray_angle_fix += 2.0 * PI;
+        let mut texture_x: f32;
+        let mut texture_y: f32;
+
+        // Drawing cells or sectors back to front, each with the walls standing in it
+        let mut next_hit = hits.len();
+        for (i, span) in spans.iter().enumerate().rev() {
+            let enter = span.enter * ray_angle_fix;
+            let exit = span.exit * ray_angle_fix;
+            let floor_height = span.floor_height;
+            let ceiling_height = span.ceiling_height;
+
+            // Drawing floor
+            if floor_height < view_z {
+                // distance of the eye to the floor, projected into texels.
+                let floor_scale = (view_z - floor_height) * 80.0 / 2.0;
+                let mp = span.floor_texture * 32 * 32;
+                let floor_top = screen_row(exit, floor_height, horizon, view_z).max(horizon + 1);
+                let floor_bottom = screen_row(enter, floor_height, horizon, view_z).min(80);
+                for y in floor_top.max(0)..floor_bottom {
+                    let delta_y = (y - horizon) as f32;
+                    let degree = span.angle;
+
+                    texture_x =
+                        span.origin_x / 2.0 + degree.cos() * floor_scale / delta_y / ray_angle_fix;
+                    texture_y =
+                        span.origin_y / 2.0 + degree.sin() * floor_scale / delta_y / ray_angle_fix;
+
+                    let pixel = (((((texture_y as usize) & 31) * 32)
+                        + ((texture_x as usize) & 31))
+                        + mp as usize)
+                        * 3;
+                    // texture coordinates are half the world position.
+                    let light = map.lighting(texture_x * 2.0, texture_y * 2.0, floor_height);
+                    let red = RGB_TEXTURES[pixel] as f32 * 0.7 * light[0] * span.tint[0];
+                    let green = RGB_TEXTURES[pixel + 1] as f32 * 0.7 * light[1] * span.tint[1];
+                    let blue = RGB_TEXTURES[pixel + 2] as f32 * 0.7 * light[2] * span.tint[2];
+                    let distance = floor_scale * 2.0 / delta_y;
+                    canvas.set_draw_color(map.fog.blend(red, green, blue, distance));
+                    //canvas.draw_point(Point::new(r * 8, y * 8))?;
+                    canvas.fill_rect(Rect::new(120 + (r * 6), 80 + (y * 6), 6, 6))?;
+                }
             }
 
-            if ray_angle_fix > (2.0 * PI) {
-                ray_angle_fix -= 2.0 * PI;
+            // Drawing ceiling
+            let mp = span.ceiling_texture * 32 * 32;
+            if ceiling_height > view_z && mp > 0 {
+                let ceiling_scale = (ceiling_height - view_z) * 80.0 / 2.0;
+                let ceiling_top = screen_row(enter, ceiling_height, horizon, view_z).max(0);
+                let ceiling_bottom =
+                    screen_row(exit, ceiling_height, horizon, view_z).min(horizon - 1);
+                for y in ceiling_top..ceiling_bottom.min(80) {
+                    let delta_y = (horizon - y) as f32;
+                    let degree = span.angle;
+
+                    texture_x = span.origin_x / 2.0
+                        + degree.cos() * ceiling_scale / delta_y / ray_angle_fix;
+                    texture_y = span.origin_y / 2.0
+                        + degree.sin() * ceiling_scale / delta_y / ray_angle_fix;
+
+                    let pixel = (((((texture_y as usize) & 31) * 32)
+                        + ((texture_x as usize) & 31))
+                        + mp as usize)
+                        * 3;
+                    let light = map.lighting(texture_x * 2.0, texture_y * 2.0, ceiling_height);
+                    let red = RGB_TEXTURES[pixel] as f32 * light[0] * span.tint[0];
+                    let green = RGB_TEXTURES[pixel + 1] as f32 * light[1] * span.tint[1];
+                    let blue = RGB_TEXTURES[pixel + 2] as f32 * light[2] * span.tint[2];
+                    let distance = ceiling_scale * 2.0 / delta_y;
+                    canvas.set_draw_color(map.fog.blend(red, green, blue, distance));
+                    //canvas.draw_point(Point::new(r * 8, y * 8))?;
+                    canvas.fill_rect(Rect::new(120 + (r * 6), 80 + (y * 6), 6, 6))?;
+                }
             }
 
-            ray_angle_fix = ray_angle_fix.cos();
-
-            texture_x =
-                player.x / 2.0 + degree.cos() * 158.0 * 0.25 * 32.0 / delta_y / ray_angle_fix;
-            texture_y =
-                player.y / 2.0 + degree.sin() * 158.0 * 0.25 * 32.0 / delta_y / ray_angle_fix;
-            let mp = map.floor_tiles
-                [((texture_y / 32.0) as i32 * map.width) as usize + (texture_x / 32.0) as usize]
-                * 32
-                * 32;
+            // a higher floor or a lower ceiling shows a step at the border.
+            if i > 0 {
+                let near_floor = spans[i - 1].floor_height;
+                let near_ceiling = spans[i - 1].ceiling_height;
+                let u = span.border_u;
+                // steps are lit from the side they face, just short of the border.
+                let border_x = span.origin_x + span.angle.cos() * (span.enter - 0.5);
+                let border_y = span.origin_y + span.angle.sin() * (span.enter - 0.5);
+
+                if floor_height > near_floor {
+                    let step = WallHit {
+                        x: border_x,
+                        y: border_y,
+                        distance: span.enter,
+                        exit: span.enter,
+                        bottom: near_floor,
+                        height: floor_height - near_floor,
+                        texture: span.floor_texture,
+                        texture_x: texture_column(u, false),
+                        shade: 0.6,
+                        see_through: false,
+                        portal: -1,
+                        mirror: -1,
+                        tint: span.tint,
+                    };
+                    draw_wall_slice(
+                        r,
+                        &step,
+                        enter,
+                        &camera,
+                        map,
+                        &mut depth[r as usize],
+                        canvas,
+                    )?;
+                }
 
-            let pixel = (((((texture_y as usize) & 31) * 32) + ((texture_x as usize) & 31))
-                + mp as usize)
-                * 3;
-            let red = (RGB_TEXTURES[pixel + 0] as f32 * 0.7) as u8;
-            let green = (RGB_TEXTURES[pixel + 1] as f32 * 0.7) as u8;
-            let blue = (RGB_TEXTURES[pixel + 2] as f32 * 0.7) as u8;
-            canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
-            //canvas.draw_point(Point::new(r * 8, y * 8))?;
-            canvas.fill_rect(Rect::new(120 + (r * 6), 80 + (y * 6), 6, 6))?;
+                let texture = span.ceiling_texture;
+                if ceiling_height < near_ceiling && texture > 0 {
+                    let step = WallHit {
+                        x: border_x,
+                        y: border_y,
+                        distance: span.enter,
+                        exit: span.enter,
+                        bottom: ceiling_height,
+                        height: near_ceiling - ceiling_height,
+                        texture,
+                        texture_x: texture_column(u, false),
+                        shade: 0.6,
+                        see_through: false,
+                        portal: -1,
+                        mirror: -1,
+                        tint: span.tint,
+                    };
+                    draw_wall_slice(
+                        r,
+                        &step,
+                        enter,
+                        &camera,
+                        map,
+                        &mut depth[r as usize],
+                        canvas,
+                    )?;
+                }
+            }
 
-            // Drawing ceiling
-            let mp = map.ceiling_tiles
-                [((texture_y / 32.0) as i32 * map.width) as usize + (texture_x / 32.0) as usize]
-                * 32
-                * 32;
-
-            let pixel = (((((texture_y as usize) & 31) * 32) + ((texture_x as usize) & 31))
-                + mp as usize)
-                * 3;
-            let red = RGB_TEXTURES[pixel + 0];
-            let green = RGB_TEXTURES[pixel + 1];
-            let blue = RGB_TEXTURES[pixel + 2];
-            if mp > 0 {
-                canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
-                //canvas.draw_point(Point::new(r * 8, (80 - y) * 8))?;
-                canvas.fill_rect(Rect::new(120 + (r * 6), 80 + ((80 - y) * 6), 6, 6))?;
+            // Drawing walls, back to front so see-through walls cover what's behind them
+            while next_hit > 0 && hits[next_hit - 1].distance >= span.enter - 0.5 {
+                let hit = &hits[next_hit - 1];
+                next_hit -= 1;
+
+                // the top of a wall below the eye shows between its near and far edge.
+                let top = hit.bottom + hit.height;
+                if top < view_z {
+                    let top_scale = (view_z - top) * 80.0 / 2.0;
+                    let top_far = screen_row(hit.exit * ray_angle_fix, top, horizon, view_z);
+                    let top_near = screen_row(hit.distance * ray_angle_fix, top, horizon, view_z);
+                    for y in top_far.max(horizon + 1).max(0)..top_near.min(80) {
+                        let delta_y = (y - horizon) as f32;
+
+                        texture_x = span.origin_x / 2.0
+                            + span.angle.cos() * top_scale / delta_y / ray_angle_fix;
+                        texture_y = span.origin_y / 2.0
+                            + span.angle.sin() * top_scale / delta_y / ray_angle_fix;
+                        let pixel = (((((texture_y as usize) & 31) * 32)
+                            + ((texture_x as usize) & 31))
+                            + hit.texture as usize * 32 * 32)
+                            * 3;
+                        let light = map.lighting(texture_x * 2.0, texture_y * 2.0, top);
+                        let red = RGB_TEXTURES[pixel] as f32 * 0.8 * light[0] * hit.tint[0];
+                        let green = RGB_TEXTURES[pixel + 1] as f32 * 0.8 * light[1] * hit.tint[1];
+                        let blue = RGB_TEXTURES[pixel + 2] as f32 * 0.8 * light[2] * hit.tint[2];
+                        let distance = top_scale * 2.0 / delta_y;
+                        canvas.set_draw_color(map.fog.blend(red, green, blue, distance));
+                        canvas.fill_rect(Rect::new(120 + (r * 6), 80 + (y * 6), 6, 6))?;
+                        depth[r as usize][y as usize] = distance as i32;
+                    }
+                }
+                draw_wall_slice(
+                    r,
+                    hit,
+                    hit.distance * ray_angle_fix,
+                    &camera,
+                    map,
+                    &mut depth[r as usize],
+                    canvas,
+                )?;
             }
         }
 
@@ -724,97 +3777,134 @@ fn draw_rays(
 fn draw_sprite(
     sprite: &mut Sprite,
     player: &Player,
-    depth: [i32; 120],
+    view: &View,
     game_state: &mut i32,
     fps: u128,
     map: &Map,
     canvas: &mut sdl2::render::Canvas<sdl2::video::Window>,
 ) -> Result<(), String> {
     if player.x < (sprite.x + 30.0)
-        && player.x > (sprite.x - 30.0)
-        && player.y < (sprite.y + 30.0)
-        && player.y > (sprite.y - 30.0)
-        && sprite.npc_type == 1
-    {
-        sprite.state = 0;
-    } else if player.x < (sprite.x + 30.0)
         && player.x > (sprite.x - 30.0)
         && player.y < (sprite.y + 30.0)
         && player.y > (sprite.y - 30.0)
         && sprite.npc_type == 3
+        && !view.god_mode
     {
         *game_state = 4;
     }
 
     if sprite.npc_type == 3 {
-        let spx = sprite.x as i32 >> 6;
-        let spy = sprite.y as i32 >> 6;
-        let spx_add = (sprite.x as i32 + 15) >> 6;
-        let spy_add = (sprite.y as i32 + 15) >> 6;
-        let spx_sub = (sprite.x as i32 - 15) >> 6;
-        let spy_sub = (sprite.y as i32 - 15) >> 6;
-
-        if sprite.x > player.x && map.wall_tiles[(spy * 8 + spx_sub) as usize] == 0 {
+        if sprite.x > player.x && chaser_can_step(map, sprite, sprite.x - 15.0, sprite.y) {
             sprite.x -= 0.03 * fps as f32;
         }
 
-        if sprite.x < player.x && map.wall_tiles[(spy * 8 + spx_add) as usize] == 0 {
+        if sprite.x < player.x && chaser_can_step(map, sprite, sprite.x + 15.0, sprite.y) {
             sprite.x += 0.03 * fps as f32;
         }
 
-        if sprite.y > player.y && map.wall_tiles[(spy_sub * 8 + spx) as usize] == 0 {
+        if sprite.y > player.y && chaser_can_step(map, sprite, sprite.x, sprite.y - 15.0) {
             sprite.y -= 0.03 * fps as f32;
         }
 
-        if sprite.y < player.y && map.wall_tiles[(spy_add * 8 + spx) as usize] == 0 {
+        if sprite.y < player.y && chaser_can_step(map, sprite, sprite.x, sprite.y + 15.0) {
             sprite.y += 0.03 * fps as f32;
         }
     }
 
-    let mut sx = sprite.x - player.x;
-    let mut sy = sprite.y - player.y;
     let sz = sprite.z;
+    let sprite_floor = map.floor_height_at(sprite.x, sprite.y);
+
+    // the sprite itself, and its reflection in every mirror on the screen
+    // that sees it from the same side as the player.
+    let mut images = vec![(sprite.x, sprite.y, None)];
+    for mirror in view.mirrors.iter().flatten() {
+        if images.iter().any(|image| image.2 == Some(*mirror)) {
+            continue;
+        }
+        let (image_x, image_y) = mirror.reflect(sprite.x, sprite.y);
+        let mut same_side = (view.y - mirror.position) * (sprite.y - mirror.position) > 0.0;
+        if mirror.vertical {
+            same_side = (view.x - mirror.position) * (sprite.x - mirror.position) > 0.0;
+        }
+        if same_side {
+            images.push((image_x, image_y, Some(*mirror)));
+        }
+    }
 
     let cs = player.angle.cos();
     let sn = player.angle.sin();
 
-    let a = sy * cs - sx * sn;
-    let b = sx * cs + sy * sn;
-    sx = a;
-    sy = b;
-
-    sx = (sx * 108.0 / sy) + (120.0 / 2.0);
-    sy = (sz * 108.0 / sy) + (80.0 / 2.0);
-
-    let mut scale = 32.0 * 80.0 / b;
-    if scale < 0.0 {
-        scale = 0.0;
-    }
-
-    if scale > 120.0 {
-        scale = 120.0;
-    }
-
-    let mut texture_x = 0.0;
-    let texture_x_step = 31.5 / scale;
-    let texture_y_step = 32.0 / scale;
-
-    for x in (sx - (scale / 2.0)) as i32..(sx + (scale / 2.0)) as i32 {
-        let mut texture_y = 31.0;
-        for y in 0..scale as i32 {
-            if x > 0 && x < 120 && (depth[x as usize] > b as i32 && sprite.state == 1) {
-                let pixel = ((texture_y as usize) * 32 + (texture_x) as usize) * 3
-                    + sprite.map as usize * 32 * 32 * 3;
-                let red = SPRITES[pixel + 0];
-                let green = SPRITES[pixel + 1];
-                let blue = SPRITES[pixel + 2];
-
-                let draw_x = 120 + (x * 6);
-                let draw_y = 80 + (sy as i32 * 6) - (y * 6);
-
-                if draw_y > 80 && draw_y < 560 {
-                    if !(red == 255 && green == 0 && blue == 255) {
-                        canvas.set_draw_color(pixels::Color::RGB(red, green, blue));
+    for (image_x, image_y, mirror) in images {
+        let mut sx = image_x - view.x;
+        let mut sy = image_y - view.y;
+
+        let a = sy * cs - sx * sn;
+        let b = sx * cs + sy * sn;
+        sx = a;
+        sy = b;
+
+        sx = (sx * 108.0 / sy) + (120.0 / 2.0);
+        // sprite heights are given relative to the standing eye height on their floor.
+        sy = (sz * 108.0 / sy)
+            + ((view.z - sprite_floor - EYE_HEIGHT) * 80.0 / b)
+            + (80.0 / 2.0)
+            + player.pitch;
+
+        let scale = (32.0 * 80.0 / b).clamp(0.0, 120.0);
+
+        // reflections are drawn the other way round and in the mirror's tint.
+        let mut texture_x = 0.0;
+        let mut texture_x_step = 31.5 / scale;
+        let mut tint = [1.0; 3];
+        if let Some(mirror) = mirror {
+            texture_x = 31.0;
+            texture_x_step = -texture_x_step;
+            tint = mirror.tint;
+        }
+        let texture_y_step = 32.0 / scale;
+        let light = map.lighting(sprite.x, sprite.y, sprite_floor + 16.0);
+
+        for x in (sx - (scale / 2.0)) as i32..(sx + (scale / 2.0)) as i32 {
+            let mut texture_y = 31.0;
+            for y in 0..scale as i32 {
+                // the depth buffer is kept per pixel, so sprites show through fences.
+                // behind a portal or mirror the column shows somewhere else, only
+                // the reflection in the column's own mirror is seen there.
+                let row = sy as i32 - y;
+                if x > 0
+                    && x < 120
+                    && row > 0
+                    && row < 80
+                    && sprite.state == 1
+                    && ((mirror.is_none() && b < view.direct[x as usize])
+                        || (mirror.is_some() && view.mirrors[x as usize] == mirror))
+                {
+                    let pixel = ((texture_y as usize) * 32 + (texture_x) as usize) * 3
+                        + sprite.map as usize * 32 * 32 * 3;
+                    let mut red = SPRITES[pixel];
+                    let mut green = SPRITES[pixel + 1];
+                    let mut blue = SPRITES[pixel + 2];
+
+                    let draw_x = 120 + (x * 6);
+                    let draw_y = 80 + (row * 6);
+
+                    if view.depth[x as usize][row as usize] > b as i32
+                        && !(red == 255 && green == 0 && blue == 255)
+                    {
+                        if sprite.key >= 0 {
+                            // keys share one texture, tinted in their colour.
+                            let (key_red, key_green, key_blue) = KEY_COLOURS[sprite.key as usize];
+                            let value = red.max(green).max(blue) as f32 / 255.0;
+                            red = (key_red as f32 * value) as u8;
+                            green = (key_green as f32 * value) as u8;
+                            blue = (key_blue as f32 * value) as u8;
+                        }
+                        canvas.set_draw_color(map.fog.blend(
+                            red as f32 * light[0] * tint[0],
+                            green as f32 * light[1] * tint[1],
+                            blue as f32 * light[2] * tint[2],
+                            b,
+                        ));
                         canvas.fill_rect(Rect::new(draw_x, draw_y, 6, 6))?;
                     }
                 }
@@ -823,9 +3913,116 @@ fn draw_sprite(
                     texture_y = 0.0;
                 }
             }
+            texture_x += texture_x_step;
         }
-        texture_x += texture_x_step;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn segment_cross_finds_diagonal_crossing() {
+        let (t, u) = segment_cross(0.0, 0.0, 1.0, 1.0, (0.0, 10.0, 10.0, 0.0)).unwrap();
+        assert!((t - 5.0).abs() < 0.001);
+        assert!((u - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn segment_cross_misses_parallel_segment() {
+        assert!(segment_cross(0.0, 0.0, 1.0, 1.0, (0.0, 10.0, 10.0, 20.0)).is_none());
+    }
+
+    #[test]
+    fn portal_carries_onto_target_face_and_back() {
+        let portals = face_portals(4, 0, FACE_SOUTH, 0, 5, FACE_EAST);
+        // the middle of the south face of (4, 0) is 8 units east of this point.
+        let (x, y) = portals[0].carry(280.0, 64.0);
+        assert!((x - 64.0).abs() < 0.001);
+        assert!((y - 344.0).abs() < 0.001);
+        let (back_x, back_y) = portals[1].carry(x, y);
+        assert!((back_x - 280.0).abs() < 0.001);
+        assert!((back_y - 64.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn portal_turns_a_ray_out_of_the_target_face() {
+        let portals = face_portals(4, 0, FACE_SOUTH, 0, 5, FACE_EAST);
+        // going north into the south face comes out going east.
+        let angle = (P3 + portals[0].turn()).rem_euclid(2.0 * PI);
+        assert!(angle < 0.001 || angle > 2.0 * PI - 0.001);
+        // and the way back turns it the other way round.
+        let total = (portals[0].turn() + portals[1].turn()).rem_euclid(2.0 * PI);
+        assert!(total < 0.001 || total > 2.0 * PI - 0.001);
+    }
+
+    #[test]
+    fn mirror_reflects_a_point_back_onto_itself() {
+        let mirrors = [
+            MirrorView {
+                vertical: true,
+                position: 128.0,
+                tint: [1.0; 3],
+            },
+            MirrorView {
+                vertical: false,
+                position: 192.0,
+                tint: [1.0; 3],
+            },
+        ];
+        for mirror in mirrors {
+            let (x, y) = mirror.reflect(100.0, 150.0);
+            assert!(x != 100.0 || y != 150.0);
+            let (back_x, back_y) = mirror.reflect(x, y);
+            assert!((back_x - 100.0).abs() < 0.001);
+            assert!((back_y - 150.0).abs() < 0.001);
+        }
+    }
+
+    #[test]
+    fn linear_fog_runs_from_start_to_end() {
+        let fog = Fog {
+            mode: FOG_LINEAR,
+            colour: (0, 0, 0),
+            start: 100.0,
+            end: 500.0,
+        };
+        assert_eq!(fog.amount(50.0), 0.0);
+        assert_eq!(fog.amount(100.0), 0.0);
+        assert!((fog.amount(300.0) - 0.5).abs() < 0.001);
+        assert_eq!(fog.amount(500.0), 1.0);
+        assert_eq!(fog.amount(900.0), 1.0);
+    }
+
+    #[test]
+    fn exponential_fog_starts_clear_and_thickens() {
+        let fog = Fog {
+            mode: FOG_EXPONENTIAL,
+            colour: (0, 0, 0),
+            start: 100.0,
+            end: 500.0,
+        };
+        assert_eq!(fog.amount(100.0), 0.0);
+        assert!((fog.amount(500.0) - (1.0 - (-3.0f32).exp())).abs() < 0.001);
+        assert!(fog.amount(900.0) > fog.amount(500.0));
+        assert!(fog.amount(900.0) < 1.0);
+    }
+
+    #[test]
+    fn light_blends_between_cell_corners() {
+        let mut map = sector_level();
+        map.sectors.clear();
+        map.width = 1;
+        map.height = 1;
+        map.light_levels = vec![0.2, 0.4, 0.6, 1.0];
+        let close = |a: f32, b: f32| (a - b).abs() < 0.001;
+        assert!(close(map.light_at(0.0, 0.0), 0.2));
+        assert!(close(map.light_at(64.0, 0.0), 0.4));
+        assert!(close(map.light_at(0.0, 64.0), 0.6));
+        assert!(close(map.light_at(64.0, 64.0), 1.0));
+        assert!(close(map.light_at(32.0, 32.0), 0.55));
+    }
+}
@@ -1,4 +1,4 @@
-pub const RGB_TEXTURES: [u8; 32 * 32 * 9 * 3] =
+pub const RGB_TEXTURES: [u8; 32 * 32 * 11 * 3] =
 [
 48,
 25,
@@ -27648,4 +27648,6148 @@ pub const RGB_TEXTURES: [u8; 32 * 32 * 9 * 3] =
 56,
 35,
 31,
+140,
+144,
+166,
+130,
+148,
+166,
+140,
+152,
+170,
+130,
+148,
+166,
+136,
+143,
+166,
+98,
+117,
+146,
+159,
+177,
+194,
+127,
+145,
+169,
+146,
+166,
+184,
+148,
+161,
+181,
+156,
+161,
+180,
+137,
+151,
+172,
+137,
+147,
+167,
+102,
+121,
+142,
+155,
+158,
+177,
+102,
+121,
+142,
+140,
+152,
+170,
+132,
+151,
+166,
+140,
+152,
+170,
+141,
+141,
+166,
+140,
+150,
+169,
+113,
+126,
+151,
+112,
+121,
+144,
+160,
+174,
+189,
+140,
+144,
+166,
+126,
+148,
+172,
+130,
+145,
+167,
+133,
+144,
+166,
+137,
+145,
+169,
+104,
+119,
+146,
+167,
+177,
+193,
+140,
+150,
+169,
+97,
+122,
+150,
+116,
+120,
+140,
+133,
+144,
+166,
+130,
+148,
+166,
+111,
+119,
+143,
+152,
+162,
+180,
+128,
+146,
+166,
+148,
+161,
+181,
+137,
+147,
+167,
+141,
+150,
+166,
+141,
+146,
+168,
+139,
+146,
+166,
+109,
+115,
+141,
+155,
+158,
+177,
+182,
+189,
+203,
+151,
+156,
+175,
+116,
+120,
+140,
+133,
+144,
+166,
+128,
+146,
+166,
+132,
+146,
+169,
+117,
+131,
+156,
+99,
+114,
+140,
+160,
+174,
+189,
+164,
+171,
+188,
+105,
+125,
+143,
+115,
+124,
+146,
+137,
+145,
+169,
+127,
+142,
+165,
+100,
+118,
+142,
+145,
+163,
+181,
+127,
+145,
+169,
+148,
+161,
+181,
+173,
+174,
+194,
+155,
+161,
+179,
+107,
+128,
+147,
+105,
+119,
+147,
+146,
+163,
+185,
+162,
+175,
+193,
+105,
+124,
+146,
+141,
+155,
+176,
+141,
+155,
+176,
+139,
+147,
+170,
+145,
+157,
+172,
+105,
+124,
+146,
+146,
+163,
+185,
+185,
+191,
+208,
+181,
+191,
+207,
+187,
+195,
+208,
+158,
+160,
+179,
+107,
+128,
+147,
+133,
+148,
+172,
+120,
+134,
+160,
+102,
+117,
+144,
+172,
+181,
+197,
+172,
+178,
+193,
+148,
+160,
+181,
+162,
+176,
+196,
+155,
+161,
+179,
+119,
+123,
+148,
+119,
+115,
+146,
+155,
+161,
+180,
+172,
+180,
+193,
+118,
+132,
+155,
+132,
+149,
+171,
+139,
+154,
+178,
+174,
+186,
+203,
+190,
+197,
+211,
+184,
+198,
+214,
+166,
+181,
+201,
+174,
+186,
+203,
+159,
+166,
+185,
+107,
+122,
+151,
+107,
+122,
+151,
+109,
+125,
+153,
+110,
+122,
+151,
+163,
+168,
+184,
+134,
+149,
+173,
+134,
+149,
+173,
+144,
+152,
+174,
+143,
+150,
+174,
+186,
+198,
+211,
+154,
+170,
+185,
+104,
+120,
+147,
+121,
+129,
+151,
+166,
+184,
+203,
+189,
+196,
+213,
+143,
+150,
+174,
+135,
+153,
+175,
+144,
+152,
+174,
+175,
+183,
+199,
+185,
+197,
+211,
+191,
+199,
+213,
+166,
+184,
+203,
+168,
+183,
+199,
+163,
+161,
+184,
+110,
+131,
+150,
+194,
+201,
+216,
+137,
+142,
+166,
+180,
+187,
+204,
+188,
+199,
+216,
+190,
+201,
+218,
+193,
+199,
+216,
+194,
+201,
+216,
+181,
+182,
+203,
+174,
+184,
+203,
+180,
+187,
+202,
+180,
+189,
+206,
+149,
+157,
+179,
+192,
+203,
+219,
+228,
+225,
+221,
+188,
+199,
+216,
+159,
+173,
+194,
+147,
+162,
+185,
+190,
+201,
+218,
+166,
+172,
+187,
+164,
+170,
+187,
+178,
+185,
+202,
+147,
+162,
+185,
+230,
+224,
+221,
+194,
+200,
+216,
+189,
+205,
+220,
+120,
+139,
+167,
+170,
+183,
+202,
+193,
+200,
+218,
+191,
+200,
+217,
+196,
+198,
+216,
+195,
+203,
+218,
+170,
+183,
+202,
+172,
+172,
+196,
+163,
+181,
+197,
+141,
+152,
+169,
+182,
+191,
+209,
+181,
+190,
+208,
+151,
+159,
+186,
+153,
+161,
+183,
+142,
+168,
+190,
+198,
+206,
+222,
+199,
+209,
+224,
+151,
+166,
+189,
+193,
+206,
+221,
+159,
+176,
+199,
+170,
+180,
+198,
+171,
+177,
+195,
+170,
+175,
+196,
+145,
+161,
+186,
+136,
+150,
+175,
+175,
+195,
+213,
+197,
+208,
+224,
+143,
+163,
+183,
+233,
+230,
+227,
+171,
+182,
+200,
+172,
+183,
+197,
+159,
+173,
+195,
+168,
+178,
+198,
+140,
+145,
+170,
+177,
+190,
+210,
+181,
+189,
+210,
+154,
+168,
+190,
+145,
+161,
+186,
+141,
+160,
+183,
+161,
+178,
+196,
+160,
+176,
+196,
+142,
+168,
+190,
+184,
+191,
+209,
+154,
+158,
+183,
+236,
+231,
+226,
+201,
+206,
+221,
+201,
+210,
+222,
+155,
+161,
+185,
+200,
+209,
+223,
+141,
+147,
+169,
+154,
+167,
+187,
+158,
+179,
+201,
+145,
+161,
+186,
+151,
+159,
+186,
+151,
+161,
+183,
+140,
+156,
+181,
+123,
+138,
+165,
+200,
+209,
+223,
+140,
+156,
+181,
+201,
+210,
+222,
+168,
+177,
+197,
+165,
+179,
+200,
+158,
+179,
+201,
+171,
+182,
+200,
+171,
+177,
+195,
+154,
+158,
+183,
+182,
+194,
+212,
+151,
+166,
+189,
+235,
+232,
+229,
+194,
+207,
+221,
+199,
+209,
+224,
+148,
+164,
+187,
+155,
+155,
+183,
+139,
+149,
+172,
+182,
+191,
+209,
+146,
+158,
+182,
+194,
+205,
+222,
+162,
+180,
+196,
+166,
+173,
+195,
+166,
+176,
+196,
+143,
+163,
+180,
+190,
+204,
+217,
+135,
+143,
+165,
+149,
+157,
+178,
+147,
+155,
+177,
+147,
+155,
+177,
+136,
+152,
+176,
+141,
+157,
+181,
+119,
+125,
+156,
+195,
+202,
+215,
+150,
+163,
+185,
+140,
+159,
+179,
+165,
+173,
+196,
+153,
+159,
+179,
+145,
+157,
+181,
+150,
+158,
+185,
+148,
+164,
+187,
+129,
+144,
+171,
+184,
+194,
+211,
+155,
+165,
+182,
+201,
+208,
+221,
+165,
+179,
+200,
+163,
+177,
+199,
+151,
+166,
+189,
+155,
+165,
+182,
+134,
+140,
+167,
+181,
+189,
+210,
+143,
+159,
+184,
+198,
+204,
+220,
+166,
+174,
+197,
+168,
+174,
+194,
+164,
+171,
+189,
+133,
+148,
+172,
+134,
+137,
+157,
+170,
+171,
+192,
+116,
+132,
+158,
+130,
+148,
+166,
+141,
+146,
+165,
+137,
+145,
+166,
+101,
+127,
+151,
+158,
+170,
+188,
+179,
+187,
+203,
+116,
+128,
+149,
+145,
+157,
+172,
+136,
+155,
+174,
+140,
+155,
+179,
+165,
+173,
+195,
+151,
+159,
+181,
+139,
+158,
+185,
+141,
+137,
+167,
+181,
+190,
+208,
+155,
+165,
+182,
+200,
+208,
+223,
+161,
+175,
+197,
+158,
+179,
+201,
+155,
+165,
+182,
+124,
+138,
+166,
+122,
+131,
+157,
+175,
+195,
+213,
+154,
+165,
+186,
+198,
+204,
+220,
+156,
+173,
+195,
+136,
+161,
+182,
+156,
+168,
+187,
+138,
+150,
+164,
+125,
+139,
+160,
+106,
+126,
+149,
+152,
+161,
+175,
+92,
+109,
+128,
+91,
+115,
+136,
+95,
+105,
+127,
+146,
+154,
+171,
+157,
+170,
+184,
+143,
+159,
+175,
+140,
+145,
+162,
+96,
+121,
+143,
+138,
+150,
+164,
+140,
+151,
+171,
+134,
+149,
+173,
+140,
+160,
+179,
+125,
+151,
+175,
+115,
+127,
+158,
+181,
+190,
+208,
+143,
+159,
+184,
+194,
+205,
+222,
+168,
+177,
+197,
+146,
+158,
+185,
+126,
+141,
+166,
+126,
+139,
+162,
+165,
+170,
+192,
+184,
+195,
+212,
+140,
+159,
+186,
+198,
+203,
+218,
+140,
+160,
+177,
+133,
+151,
+170,
+127,
+141,
+163,
+117,
+130,
+151,
+120,
+127,
+141,
+94,
+107,
+128,
+121,
+127,
+140,
+125,
+132,
+146,
+129,
+136,
+148,
+126,
+135,
+148,
+106,
+114,
+129,
+102,
+111,
+130,
+101,
+120,
+136,
+133,
+140,
+153,
+131,
+130,
+148,
+105,
+115,
+129,
+95,
+113,
+141,
+107,
+118,
+147,
+111,
+123,
+152,
+126,
+138,
+156,
+159,
+175,
+193,
+184,
+193,
+210,
+155,
+161,
+181,
+199,
+209,
+224,
+145,
+161,
+186,
+140,
+159,
+186,
+161,
+178,
+193,
+170,
+168,
+192,
+185,
+186,
+208,
+181,
+190,
+208,
+125,
+143,
+167,
+153,
+165,
+181,
+157,
+170,
+190,
+125,
+139,
+161,
+127,
+135,
+149,
+112,
+120,
+136,
+121,
+121,
+121,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+142,
+159,
+177,
+151,
+170,
+189,
+167,
+171,
+191,
+176,
+191,
+208,
+179,
+192,
+211,
+136,
+142,
+166,
+143,
+159,
+184,
+170,
+177,
+196,
+143,
+159,
+184,
+195,
+206,
+223,
+193,
+205,
+223,
+201,
+203,
+221,
+182,
+194,
+212,
+114,
+131,
+160,
+135,
+142,
+163,
+141,
+150,
+171,
+122,
+138,
+158,
+107,
+112,
+129,
+86,
+93,
+110,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+194,
+194,
+194,
+171,
+176,
+191,
+180,
+190,
+206,
+192,
+203,
+219,
+200,
+210,
+225,
+183,
+188,
+208,
+122,
+131,
+157,
+139,
+149,
+172,
+151,
+166,
+189,
+151,
+162,
+184,
+155,
+168,
+184,
+145,
+161,
+186,
+140,
+145,
+166,
+199,
+209,
+224,
+185,
+186,
+208,
+123,
+131,
+154,
+106,
+117,
+146,
+104,
+112,
+133,
+87,
+99,
+119,
+75,
+80,
+94,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+121,
+134,
+155,
+134,
+148,
+171,
+139,
+155,
+179,
+130,
+141,
+170,
+198,
+205,
+223,
+178,
+188,
+209,
+126,
+135,
+158,
+110,
+130,
+156,
+126,
+139,
+162,
+236,
+232,
+228,
+201,
+206,
+222,
+155,
+161,
+181,
+141,
+152,
+169,
+192,
+207,
+224,
+179,
+188,
+201,
+163,
+172,
+191,
+130,
+140,
+158,
+112,
+121,
+136,
+96,
+97,
+109,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+115,
+238,
+95,
+115,
+238,
+95,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+165,
+170,
+183,
+216,
+211,
+207,
+188,
+200,
+215,
+155,
+161,
+181,
+129,
+150,
+168,
+198,
+206,
+222,
+180,
+188,
+207,
+184,
+193,
+207,
+156,
+175,
+198,
+171,
+177,
+198,
+172,
+183,
+197,
+170,
+180,
+198,
+151,
+159,
+186,
+184,
+191,
+207,
+188,
+199,
+217,
+130,
+148,
+166,
+127,
+135,
+149,
+102,
+111,
+130,
+83,
+89,
+101,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+115,
+238,
+95,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+115,
+238,
+95,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+135,
+147,
+164,
+153,
+161,
+179,
+163,
+176,
+195,
+170,
+177,
+196,
+145,
+161,
+186,
+178,
+188,
+208,
+192,
+207,
+224,
+154,
+164,
+185,
+143,
+163,
+183,
+172,
+172,
+196,
+168,
+176,
+199,
+171,
+182,
+200,
+154,
+167,
+187,
+181,
+189,
+210,
+144,
+159,
+182,
+214,
+210,
+207,
+193,
+189,
+187,
+138,
+146,
+157,
+110,
+114,
+121,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+115,
+238,
+95,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+134,
+145,
+163,
+153,
+160,
+181,
+159,
+172,
+194,
+160,
+176,
+196,
+151,
+166,
+189,
+176,
+191,
+208,
+145,
+161,
+186,
+236,
+230,
+226,
+234,
+230,
+227,
+154,
+158,
+183,
+140,
+159,
+186,
+154,
+158,
+183,
+123,
+142,
+171,
+174,
+187,
+207,
+137,
+156,
+178,
+183,
+189,
+201,
+134,
+145,
+163,
+112,
+124,
+140,
+88,
+100,
+111,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+115,
+238,
+95,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+138,
+143,
+161,
+140,
+150,
+169,
+151,
+157,
+180,
+149,
+157,
+182,
+138,
+145,
+167,
+184,
+191,
+209,
+155,
+165,
+182,
+199,
+207,
+222,
+163,
+177,
+199,
+140,
+159,
+186,
+167,
+174,
+196,
+129,
+144,
+171,
+126,
+128,
+156,
+184,
+191,
+207,
+144,
+159,
+182,
+156,
+166,
+179,
+131,
+144,
+161,
+120,
+128,
+142,
+85,
+91,
+100,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+115,
+238,
+95,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+140,
+149,
+164,
+130,
+145,
+167,
+163,
+171,
+194,
+122,
+147,
+175,
+112,
+133,
+156,
+185,
+195,
+209,
+149,
+157,
+182,
+168,
+181,
+201,
+168,
+181,
+201,
+126,
+141,
+169,
+140,
+145,
+166,
+118,
+134,
+162,
+156,
+175,
+198,
+201,
+206,
+221,
+124,
+128,
+155,
+141,
+150,
+166,
+119,
+136,
+150,
+109,
+109,
+128,
+79,
+89,
+100,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+115,
+238,
+95,
+115,
+238,
+95,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+110,
+115,
+137,
+124,
+129,
+151,
+121,
+134,
+161,
+109,
+125,
+154,
+170,
+176,
+192,
+196,
+205,
+222,
+114,
+131,
+160,
+143,
+159,
+184,
+143,
+159,
+184,
+128,
+136,
+155,
+108,
+129,
+160,
+166,
+172,
+195,
+197,
+208,
+224,
+200,
+205,
+221,
+158,
+166,
+189,
+116,
+124,
+141,
+89,
+102,
+126,
+88,
+95,
+113,
+70,
+68,
+86,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+115,
+238,
+95,
+115,
+238,
+95,
+115,
+238,
+95,
+115,
+238,
+95,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+93,
+107,
+131,
+99,
+114,
+140,
+113,
+124,
+151,
+164,
+175,
+196,
+199,
+206,
+221,
+200,
+209,
+223,
+170,
+174,
+191,
+116,
+128,
+155,
+128,
+123,
+156,
+166,
+172,
+192,
+167,
+174,
+194,
+151,
+162,
+184,
+141,
+160,
+183,
+146,
+158,
+182,
+147,
+155,
+177,
+166,
+175,
+191,
+131,
+142,
+159,
+115,
+123,
+139,
+96,
+102,
+113,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+67,
+255,
+36,
+72,
+212,
+49,
+194,
+194,
+194,
+140,
+141,
+159,
+155,
+159,
+175,
+164,
+171,
+189,
+140,
+159,
+186,
+141,
+160,
+183,
+151,
+166,
+189,
+151,
+166,
+189,
+181,
+190,
+208,
+159,
+179,
+199,
+155,
+161,
+185,
+195,
+206,
+223,
+234,
+231,
+227,
+197,
+208,
+224,
+172,
+180,
+196,
+138,
+163,
+185,
+136,
+151,
+172,
+153,
+164,
+179,
+130,
+141,
+153,
+91,
+100,
+114,
+121,
+121,
+121,
+194,
+194,
+194,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+72,
+212,
+49,
+194,
+194,
+194,
+167,
+174,
+187,
+139,
+147,
+171,
+190,
+200,
+217,
+233,
+231,
+229,
+200,
+209,
+223,
+170,
+177,
+196,
+154,
+167,
+187,
+155,
+161,
+185,
+185,
+195,
+209,
+171,
+182,
+200,
+172,
+180,
+196,
+170,
+180,
+198,
+159,
+173,
+195,
+170,
+180,
+199,
+155,
+172,
+194,
+103,
+121,
+146,
+168,
+178,
+192,
+114,
+127,
+144,
+155,
+153,
+149,
+121,
+121,
+121,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+194,
+167,
+176,
+191,
+157,
+163,
+183,
+164,
+171,
+191,
+170,
+177,
+196,
+168,
+177,
+197,
+163,
+181,
+197,
+163,
+181,
+197,
+123,
+133,
+158,
+198,
+204,
+221,
+145,
+166,
+183,
+153,
+161,
+183,
+139,
+163,
+189,
+143,
+163,
+183,
+145,
+161,
+186,
+126,
+121,
+154,
+175,
+183,
+199,
+124,
+145,
+168,
+132,
+143,
+161,
+124,
+131,
+145,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+121,
+162,
+168,
+184,
+147,
+153,
+172,
+141,
+160,
+180,
+140,
+159,
+186,
+155,
+155,
+183,
+155,
+161,
+181,
+109,
+125,
+154,
+183,
+191,
+208,
+155,
+161,
+185,
+126,
+139,
+162,
+126,
+139,
+162,
+145,
+161,
+186,
+148,
+164,
+187,
+112,
+133,
+156,
+154,
+173,
+196,
+148,
+155,
+177,
+152,
+163,
+184,
+133,
+141,
+161,
+117,
+130,
+151,
+110,
+123,
+142,
+111,
+119,
+135,
+82,
+91,
+113,
+112,
+126,
+140,
+136,
+145,
+155,
+110,
+119,
+134,
+86,
+93,
+111,
+109,
+118,
+129,
+101,
+120,
+136,
+111,
+117,
+134,
+94,
+114,
+135,
+88,
+110,
+135,
+156,
+165,
+184,
+165,
+175,
+193,
+107,
+135,
+160,
+111,
+131,
+154,
+154,
+168,
+190,
+139,
+163,
+189,
+114,
+131,
+160,
+170,
+179,
+194,
+151,
+166,
+189,
+160,
+183,
+202,
+170,
+183,
+202,
+155,
+171,
+188,
+120,
+130,
+154,
+123,
+125,
+152,
+166,
+172,
+187,
+169,
+184,
+204,
+121,
+123,
+149,
+131,
+148,
+174,
+132,
+146,
+169,
+131,
+144,
+165,
+130,
+135,
+153,
+104,
+112,
+132,
+128,
+141,
+156,
+161,
+168,
+178,
+157,
+164,
+178,
+157,
+164,
+178,
+125,
+138,
+156,
+102,
+112,
+126,
+125,
+125,
+148,
+112,
+119,
+138,
+93,
+118,
+140,
+155,
+164,
+182,
+165,
+174,
+190,
+153,
+164,
+183,
+176,
+185,
+202,
+157,
+166,
+186,
+123,
+136,
+158,
+105,
+126,
+156,
+166,
+172,
+187,
+172,
+185,
+204,
+111,
+128,
+156,
+140,
+159,
+179,
+147,
+158,
+178,
+171,
+184,
+202,
+186,
+198,
+211,
+190,
+197,
+211,
+175,
+183,
+199,
+166,
+186,
+203,
+158,
+168,
+187,
+105,
+120,
+148,
+116,
+122,
+144,
+102,
+116,
+143,
+110,
+114,
+138,
+145,
+154,
+173,
+125,
+143,
+160,
+135,
+140,
+161,
+124,
+146,
+166,
+124,
+146,
+166,
+172,
+179,
+193,
+148,
+156,
+169,
+111,
+115,
+134,
+99,
+112,
+139,
+159,
+168,
+187,
+178,
+186,
+201,
+142,
+152,
+172,
+144,
+154,
+174,
+141,
+148,
+172,
+175,
+186,
+202,
+191,
+199,
+213,
+191,
+196,
+211,
+175,
+180,
+199,
+176,
+185,
+201,
+163,
+168,
+184,
+104,
+120,
+147,
+180,
+190,
+206,
+120,
+140,
+157,
+171,
+179,
+195,
+185,
+190,
+206,
+180,
+191,
+208,
+188,
+189,
+206,
+181,
+194,
+206,
+169,
+177,
+194,
+168,
+179,
+196,
+169,
+177,
+193,
+165,
+173,
+189,
+140,
+143,
+166,
+182,
+191,
+204,
+213,
+210,
+207,
+175,
+185,
+200,
+152,
+158,
+178,
+140,
+153,
+172,
+179,
+189,
+203,
+151,
+157,
+174,
+144,
+159,
+175,
+168,
+176,
+188,
+134,
+145,
+170,
+217,
+213,
+209,
+184,
+190,
+207,
+181,
+191,
+207,
+126,
+133,
+160,
+172,
+180,
+196,
+187,
+196,
+210,
+181,
+193,
+206,
+183,
+191,
+206,
+186,
+192,
+206,
+163,
+177,
+194,
+153,
+161,
+179,
+145,
+157,
+177,
+114,
+138,
+160,
+162,
+171,
+190,
+166,
+172,
+188,
+141,
+150,
+166,
+129,
+153,
+173,
+140,
+152,
+170,
+176,
+187,
+201,
+182,
+189,
+202,
+133,
+144,
+168,
+180,
+187,
+203,
+149,
+159,
+180,
+146,
+159,
+179,
+148,
+161,
+181,
+146,
+159,
+179,
+141,
+150,
+166,
+116,
+128,
+153,
+166,
+175,
+190,
+182,
+187,
+201,
+135,
+149,
+170,
+215,
+210,
+207,
+155,
+159,
+178,
+150,
+163,
+182,
+146,
+160,
+178,
+156,
+161,
+180,
+113,
+126,
+151,
+167,
+174,
+188,
+160,
+172,
+190,
+139,
+146,
+166,
+135,
+149,
+170,
+137,
+147,
+167,
+153,
+162,
+180,
+148,
+165,
+179,
+140,
+149,
+168,
+161,
+173,
+191,
+127,
+142,
+165,
+214,
+209,
+206,
+177,
+187,
+203,
+175,
+188,
+204,
+141,
+150,
+166,
+180,
+186,
+201,
+126,
+134,
+155,
+132,
+151,
+166,
+156,
+161,
+177,
+128,
+146,
+166,
+141,
+146,
+165,
+139,
+146,
+166,
+140,
+149,
+168,
+116,
+127,
+144,
+183,
+189,
+201,
+132,
+146,
+169,
+183,
+187,
+202,
+144,
+163,
+183,
+153,
+159,
+178,
+146,
+166,
+184,
+155,
+164,
+181,
+155,
+164,
+180,
+132,
+146,
+169,
+162,
+177,
+189,
+140,
+150,
+169,
+215,
+209,
+206,
+176,
+186,
+201,
+176,
+186,
+201,
+133,
+144,
+166,
+141,
+153,
+167,
+118,
+128,
+155,
+167,
+177,
+192,
+136,
+143,
+166,
+179,
+189,
+204,
+153,
+165,
+183,
+146,
+162,
+178,
+155,
+164,
+180,
+126,
+148,
+172,
+178,
+187,
+201,
+120,
+134,
+157,
+139,
+146,
+166,
+137,
+151,
+172,
+137,
+146,
+166,
+135,
+149,
+170,
+141,
+146,
+165,
+109,
+115,
+141,
+176,
+186,
+201,
+127,
+142,
+165,
+129,
+153,
+173,
+148,
+165,
+179,
+137,
+145,
+169,
+130,
+148,
+166,
+140,
+152,
+170,
+126,
+148,
+172,
+111,
+134,
+159,
+159,
+177,
+194,
+140,
+150,
+169,
+181,
+188,
+202,
+149,
+159,
+180,
+156,
+164,
+178,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+140,
+140,
+150,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+95,
+95,
+105,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+140,
+140,
+150,
+95,
+95,
+105,
+60,
+60,
+70,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
+255,
+0,
+255,
 ];